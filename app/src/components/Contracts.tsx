@@ -6,9 +6,11 @@ import { Badge, Flex, Skeleton } from "@mantine/core";
 const limit = 30;
 
 export default function Contracts() {
-  const [page, setPage] = useState(0);
+  // cursors of every visited page, the last one is the current page
+  const [cursors, setCursors] = useState<(string | undefined)[]>([undefined]);
+  const page = cursors.length - 1;
   const contracts = whitelistQueries.useApprovedContracts({
-    fromIndex: limit * page,
+    cursor: cursors[page],
     limit,
   });
 
@@ -19,20 +21,13 @@ export default function Contracts() {
       page={page + 1}
       title="Approved Contracts"
       onClickNext={() => {
-        if (contracts.data?.length !== 0) {
-          setPage((i) => {
-            return i + 1;
-          });
+        const nextCursor = contracts.data?.next_cursor;
+        if (nextCursor) {
+          setCursors((i) => [...i, nextCursor]);
         }
       }}
       onClickPrev={() => {
-        setPage((i) => {
-          if (i !== 0) {
-            return i - 1;
-          } else {
-            return i;
-          }
-        });
+        setCursors((i) => (i.length > 1 ? i.slice(0, -1) : i));
       }}
     >
       <Flex gap="md" wrap="wrap">
@@ -43,7 +38,7 @@ export default function Contracts() {
             <Skeleton height={25} w={160} radius={20} />
           </>
         )}
-        {contracts.data?.map(([contract_id]) => {
+        {contracts.data?.items.map(([contract_id]) => {
           return (
             <Badge
               tt={"none"}
@@ -31,9 +31,11 @@ const limit = 6;
 
 export default function Projects() {
   const router = useRouter();
-  const [page, setPage] = useState(0);
+  // cursors of every visited page, the last one is the current page
+  const [cursors, setCursors] = useState<(string | undefined)[]>([undefined]);
+  const page = cursors.length - 1;
   const projects = whitelistQueries.useApprovedProjects({
-    fromIndex: limit * page,
+    cursor: cursors[page],
     limit,
   });
 
@@ -49,20 +51,13 @@ export default function Projects() {
       page={page + 1}
       title="Approved Projects"
       onClickNext={() => {
-        if (projects.data?.length !== 0) {
-          setPage((i) => {
-            return i + 1;
-          });
+        const nextCursor = projects.data?.next_cursor;
+        if (nextCursor) {
+          setCursors((i) => [...i, nextCursor]);
         }
       }}
       onClickPrev={() => {
-        setPage((i) => {
-          if (i !== 0) {
-            return i - 1;
-          } else {
-            return i;
-          }
-        });
+        setCursors((i) => (i.length > 1 ? i.slice(0, -1) : i));
       }}
     >
       <SimpleGrid
@@ -78,7 +73,7 @@ export default function Projects() {
             <Skeleton h={170} />
           </>
         )}
-        {projects.data?.map(([project_id, project_info]) => {
+        {projects.data?.items.map(([project_id, project_info]) => {
           const openInNewTab = (url: string) => {
             window.open(url, "_blank");
           };
@@ -1,5 +1,9 @@
 import { whitelistMutate } from "@/hooks/whitelistMutate";
-import { EProjectKind, IProposal } from "@/types/whitelist.types";
+import {
+  EProjectKind,
+  EProposalStatus,
+  IProposal,
+} from "@/types/whitelist.types";
 import {
   ActionIcon,
   Badge,
@@ -33,6 +37,17 @@ export default function ProposalCard({
 }) {
   const voteProposal = whitelistMutate.useVoteProposal();
   const withdrawVote = whitelistMutate.useWithdrawVoteOnProposal();
+  const executeProposal = whitelistMutate.useExecuteProposal();
+
+  // executable_at is in nanoseconds, set once the proposal passed
+  const executableAt =
+    proposalInfo.executable_at !== null
+      ? new Date(Number(proposalInfo.executable_at) / 1e6)
+      : undefined;
+  const isExecutable =
+    proposalInfo.status === EProposalStatus.PASSED &&
+    executableAt !== undefined &&
+    executableAt.getTime() <= Date.now();
 
   const { project_info } = proposalInfo;
   const metadata = JSON.parse(project_info.metadata) as {
@@ -180,27 +195,44 @@ export default function ProposalCard({
           Compare
         </Button>
       )}
-      {proposalInfo.votes.includes(currentWalletId || "") ? (
-        <Button
-          disabled={currentWalletId === undefined || !isOneOfGuardians}
-          loading={withdrawVote.status === "pending"}
-          onClick={() => {
-            withdrawVote.mutate(proposalId);
-          }}
-        >
-          Withdraw Vote ({proposalInfo.votes.length})
-        </Button>
-      ) : (
-        <Button
-          disabled={currentWalletId === undefined || !isOneOfGuardians}
-          loading={voteProposal.status === "pending"}
-          onClick={() => {
-            voteProposal.mutate(proposalId);
-          }}
-        >
-          Vote ({proposalInfo.votes.length})
-        </Button>
-      )}
+      {proposalInfo.status === EProposalStatus.PASSED &&
+        (isExecutable ? (
+          <Button
+            disabled={currentWalletId === undefined}
+            loading={executeProposal.status === "pending"}
+            onClick={() => {
+              executeProposal.mutate(proposalId);
+            }}
+          >
+            Execute
+          </Button>
+        ) : (
+          <Text size="sm" c="dimmed" ta="center">
+            Executable from {executableAt?.toLocaleString()}
+          </Text>
+        ))}
+      {proposalInfo.status === EProposalStatus.PENDING &&
+        (proposalInfo.votes.includes(currentWalletId || "") ? (
+          <Button
+            disabled={currentWalletId === undefined || !isOneOfGuardians}
+            loading={withdrawVote.status === "pending"}
+            onClick={() => {
+              withdrawVote.mutate(proposalId);
+            }}
+          >
+            Withdraw Vote ({proposalInfo.votes.length})
+          </Button>
+        ) : (
+          <Button
+            disabled={currentWalletId === undefined || !isOneOfGuardians}
+            loading={voteProposal.status === "pending"}
+            onClick={() => {
+              voteProposal.mutate(proposalId);
+            }}
+          >
+            Vote ({proposalInfo.votes.length})
+          </Button>
+        ))}
     </Card>
   );
 }
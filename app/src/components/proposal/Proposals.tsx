@@ -32,7 +32,9 @@ import ProposalCard from "./ProposalCard";
 const limit = 6;
 
 export default function Proposals() {
-  const [page, setPage] = useState(0);
+  // cursors of every visited page, the last one is the current page
+  const [cursors, setCursors] = useState<(string | undefined)[]>([undefined]);
+  const page = cursors.length - 1;
 
   const [opened, { open, close }] = useDisclosure(false);
   const [comparisonInfo, setComparisonInfo] = useState<{
@@ -43,8 +45,9 @@ export default function Proposals() {
     newProjectInfo: undefined,
   });
 
+  // newest first, as listed by the contract
   const proposals = whitelistQueries.useProposals({
-    fromIndex: limit * page,
+    cursor: cursors[page],
     limit,
   });
 
@@ -66,20 +69,13 @@ export default function Proposals() {
         page={page + 1}
         title="Pending Proposals"
         onClickNext={() => {
-          if (proposals.data?.length !== 0) {
-            setPage((i) => {
-              return i + 1;
-            });
+          const nextCursor = proposals.data?.next_cursor;
+          if (nextCursor) {
+            setCursors((i) => [...i, nextCursor]);
           }
         }}
         onClickPrev={() => {
-          setPage((i) => {
-            if (i !== 0) {
-              return i - 1;
-            } else {
-              return i;
-            }
-          });
+          setCursors((i) => (i.length > 1 ? i.slice(0, -1) : i));
         }}
       >
         <SimpleGrid
@@ -95,7 +91,7 @@ export default function Proposals() {
               <Skeleton h={170} />
             </>
           )}
-          {proposals.data?.map(([proposal_id, proposal_info]) => {
+          {proposals.data?.items.map(([proposal_id, proposal_info]) => {
             return (
               <ProposalCard
                 key={proposal_id}
@@ -119,6 +119,65 @@ const useWithdrawVoteOnProposal = () => {
   });
 };
 
+const useExecuteProposal = () => {
+  const walletSelector = useAtomValue(walletSelectorAtom);
+
+  return useMutation({
+    mutationFn: async (proposal_id: string) => {
+      if (walletSelector) {
+        const wallet = await walletSelector.wallet();
+        if (wallet) {
+          await wallet.signAndSendTransaction({
+            receiverId: CONTRACT_ID_BY_NETWORK[CURRENT_NEAR_NETWORK],
+            actions: [
+              {
+                type: "FunctionCall",
+                params: {
+                  methodName: "execute_proposal",
+                  args: {
+                    proposal_id,
+                  },
+                  // large proposals are executed in chunks of up to 100 contracts per call
+                  gas: utils.format.parseNearAmount("0.0000000003")!,
+                  deposit: "0",
+                },
+              },
+            ],
+          });
+        } else {
+          throw new Error("Please login with a wallet first");
+        }
+      } else {
+        throw new Error("Wallet selector is not initialized.");
+      }
+    },
+    onSuccess: (data, proposal_id) => {
+      browserQueryClient?.invalidateQueries({
+        queryKey: [EQueryKeys.APPROVED_CONTRACTS],
+      });
+      browserQueryClient?.invalidateQueries({
+        queryKey: [EQueryKeys.APPROVED_PROJECTS],
+      });
+      browserQueryClient?.invalidateQueries({
+        queryKey: [EQueryKeys.PROPOSALS],
+      });
+      browserQueryClient?.invalidateQueries({
+        queryKey: [
+          EQueryKeys.PROPOSAL_BY_ID,
+          { network: CURRENT_NEAR_NETWORK, proposalId: proposal_id },
+        ],
+      });
+    },
+    onError: (err) => {
+      notifications.show({
+        title: "Error",
+        message: err.message,
+        color: "red",
+      });
+    },
+  });
+};
+
 const useAddProject = () => {
   const walletSelector = useAtomValue(walletSelectorAtom);
 
@@ -232,6 +291,7 @@ const useUpdateProject = () => {
 export const whitelistMutate = {
   useVoteProposal,
   useWithdrawVoteOnProposal,
+  useExecuteProposal,
   useAddProject,
   useUpdateProject,
 };
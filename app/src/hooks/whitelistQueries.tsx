@@ -9,7 +9,7 @@ import {
   useQuery,
 } from "@tanstack/react-query";
 import { CURRENT_NEAR_NETWORK } from "@/constant/nearConstant";
-import { IProjectInfo, IProposal } from "@/types/whitelist.types";
+import { IPage, IProjectInfo, IProposal } from "@/types/whitelist.types";
 
 const useGuardians = () => {
   const network = CURRENT_NEAR_NETWORK;
@@ -33,26 +33,26 @@ const useGuardians = () => {
 
 const useApprovedProjects = ({
   limit,
-  fromIndex,
+  cursor,
 }: {
   limit: number;
-  fromIndex: number;
+  cursor?: string;
 }) => {
   const network = CURRENT_NEAR_NETWORK;
 
   return useQuery({
-    queryKey: [EQueryKeys.APPROVED_PROJECTS, { limit, fromIndex, network }],
+    queryKey: [EQueryKeys.APPROVED_PROJECTS, { limit, cursor, network }],
     queryFn: async () => {
       const near = await nearUtils.getNear({
         network,
       });
 
-      const outcome: [string, IProjectInfo][] = await near.viewFunction({
+      const outcome: IPage<[string, IProjectInfo]> = await near.viewFunction({
         methodName: "list_projects",
         contractId: CONTRACT_ID_BY_NETWORK[network],
         args: {
           limit,
-          from_index: fromIndex,
+          cursor,
         },
       });
 
@@ -64,26 +64,26 @@ const useApprovedProjects = ({
 
 const useApprovedContracts = ({
   limit,
-  fromIndex,
+  cursor,
 }: {
   limit: number;
-  fromIndex: number;
+  cursor?: string;
 }) => {
   const network = CURRENT_NEAR_NETWORK;
 
   return useQuery({
-    queryKey: [EQueryKeys.APPROVED_CONTRACTS, { limit, fromIndex, network }],
+    queryKey: [EQueryKeys.APPROVED_CONTRACTS, { limit, cursor, network }],
     queryFn: async () => {
       const near = await nearUtils.getNear({
         network,
       });
 
-      const outcome: [string, string][] = await near.viewFunction({
+      const outcome: IPage<[string, string]> = await near.viewFunction({
         methodName: "list_contracts",
         contractId: CONTRACT_ID_BY_NETWORK[network],
         args: {
           limit,
-          from_index: fromIndex,
+          cursor,
         },
       });
 
@@ -95,26 +95,26 @@ const useApprovedContracts = ({
 
 const useProposals = ({
   limit,
-  fromIndex,
+  cursor,
 }: {
   limit: number;
-  fromIndex: number;
+  cursor?: string;
 }) => {
   const network = CURRENT_NEAR_NETWORK;
 
   return useQuery({
-    queryKey: [EQueryKeys.PROPOSALS, { limit, fromIndex, network }],
+    queryKey: [EQueryKeys.PROPOSALS, { limit, cursor, network }],
     queryFn: async () => {
       const near = await nearUtils.getNear({
         network,
       });
 
-      const outcome: [string, IProposal][] = await near.viewFunction({
+      const outcome: IPage<[string, IProposal]> = await near.viewFunction({
         methodName: "list_proposals",
         contractId: CONTRACT_ID_BY_NETWORK[network],
         args: {
           limit,
-          from_index: fromIndex,
+          cursor,
         },
       });
 
@@ -8,6 +8,61 @@ export interface IProjectInfo {
 export enum EProjectKind {
   NEW = "NEW",
   UPDATE = "UPDATE",
+  TRANSFER = "TRANSFER",
+  MERGE = "MERGE",
+  SPLIT = "SPLIT",
+  RENAME = "RENAME",
+  REMOVE = "REMOVE",
+  UNPAUSE = "UNPAUSE",
+  UPGRADE = "UPGRADE",
+  DENYLIST = "DENYLIST",
+  UNDENYLIST = "UNDENYLIST",
+}
+
+export enum EProposalStatus {
+  PENDING = "PENDING",
+  PASSED = "PASSED",
+  APPROVED = "APPROVED",
+  REJECTED = "REJECTED",
+  CANCELLED = "CANCELLED",
+  EXPIRED = "EXPIRED",
+  VETOED = "VETOED",
+}
+
+export enum EPauseScope {
+  SUBMISSIONS = "SUBMISSIONS",
+  VOTING = "VOTING",
+  EXECUTION = "EXECUTION",
+}
+
+export enum EDenylistReason {
+  PHISHING = "PHISHING",
+  DRAINER = "DRAINER",
+  RUGPULL = "RUGPULL",
+  IMPERSONATION = "IMPERSONATION",
+  EXPLOIT = "EXPLOIT",
+  OTHER = "OTHER",
+}
+
+export enum EVerdict {
+  TRUSTED = "TRUSTED",
+  UNKNOWN = "UNKNOWN",
+  MALICIOUS = "MALICIOUS",
+}
+
+export interface IProposalAmendment {
+  contract_ids: string[];
+  metadata: string;
+  votes: string[];
+  rejections: string[];
+  amended_at: string;
+}
+
+export interface IProposalComment {
+  account_id: string;
+  action: "APPROVE" | "REJECT" | null;
+  comment: string;
+  created_at: string;
 }
 
 export interface IProposal {
@@ -16,4 +71,42 @@ export interface IProposal {
   project_info: IProjectInfo;
   proposed_by: string;
   votes: string[];
+  status: EProposalStatus;
+  rejections: string[];
+  created_at: string;
+  related_project: IProjectInfo | null;
+  base_versions: Record<string, number>;
+  stale: boolean;
+  amendments: IProposalAmendment[];
+  comments: IProposalComment[];
+  delegated_votes: Record<string, string>;
+  tally: { approval_weight: number; rejection_weight: number };
+  executable_at: string | null;
+  pause_scopes: EPauseScope[] | null;
+  upgrade: { code_hash: string; migrate_method: string } | null;
+  denylist: {
+    contract_ids: string[];
+    evidence: { reason: EDenylistReason; evidence_urls: string[] } | null;
+  } | null;
+}
+
+export interface IPage<T> {
+  items: T[];
+  next_cursor: string | null;
+  max_page_size: number;
+  // null for list_proposals_awaiting_vote
+  total: number | null;
+}
+
+export interface IScreeningResult {
+  verdict: "TRUSTED" | "UNKNOWN" | "SUSPENDED" | "DENYLISTED";
+  project_id: string | null;
+  tier: "AUDITED" | "LISTED" | null;
+  warnings: (
+    | "AUDIT_EXPIRED"
+    | "PENDING_REMOVAL"
+    | "OPEN_REPORTS"
+    | "DEPOSIT_TO_UNTRUSTED"
+    | "TOKEN_APPROVAL"
+  )[];
 }
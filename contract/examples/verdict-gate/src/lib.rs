@@ -0,0 +1,43 @@
+// Example integrator: only lets through accounts the whitelist reports as TRUSTED.
+use near_sdk::{env, near, AccountId, PanicOnDefault, Promise, PromiseError};
+use near_whitelist_ext::{ext_whitelist, EVerdict, VerdictResponse};
+
+#[near(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct VerdictGate {
+    whitelist_id: AccountId,
+    // verdict of the last query, null if the query failed
+    last_verdict: Option<EVerdict>,
+}
+
+#[near]
+impl VerdictGate {
+    #[init]
+    pub fn new(whitelist_id: AccountId) -> Self {
+        Self {
+            whitelist_id,
+            last_verdict: None,
+        }
+    }
+
+    // Resolves to whether the account is trusted.
+    pub fn check_account(&mut self, account_id: AccountId) -> Promise {
+        ext_whitelist::ext(self.whitelist_id.clone())
+            .query_verdict(account_id)
+            .then(Self::ext(env::current_account_id()).on_verdict())
+    }
+
+    #[private]
+    pub fn on_verdict(
+        &mut self,
+        #[callback_result] response: Result<VerdictResponse, PromiseError>,
+    ) -> bool {
+        // a failed query is treated as untrusted
+        self.last_verdict = response.ok().map(|response| response.verdict);
+        self.last_verdict == Some(EVerdict::TRUSTED)
+    }
+
+    pub fn get_last_verdict(&self) -> Option<EVerdict> {
+        self.last_verdict
+    }
+}
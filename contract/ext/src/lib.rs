@@ -0,0 +1,35 @@
+// Enum variants are upper case on purpose, they are part of the JSON interface.
+#![allow(clippy::upper_case_acronyms)]
+
+use near_sdk::json_types::U64;
+use near_sdk::{ext_contract, near, AccountId};
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EVerdict {
+    // whitelisted by an approved project that is not suspended
+    TRUSTED,
+    UNKNOWN,
+    // denylisted, whether whitelisted or not
+    MALICIOUS,
+}
+
+#[near(serializers=[json])]
+pub struct VerdictResponse {
+    pub account_id: AccountId,
+    pub verdict: EVerdict,
+    // null when the account is not whitelisted
+    pub project_id: Option<String>,
+    // block timestamp of the query
+    pub checked_at: U64,
+}
+
+// Interface for other contracts gating calls on the whitelist, e.g.
+//   ext_whitelist::ext(whitelist_id).query_verdict(account_id).then(<callback reading the VerdictResponse>)
+// See examples/verdict-gate for a complete contract.
+#[ext_contract(ext_whitelist)]
+pub trait Whitelist {
+    fn query_verdict(&self, account_id: AccountId) -> VerdictResponse;
+    fn check_contract_whitelisted(&self, contract_id: AccountId) -> bool;
+    fn get_contract_verdict(&self, contract_id: AccountId) -> EVerdict;
+}
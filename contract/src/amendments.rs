@@ -0,0 +1,108 @@
+use crate::diff::changed_metadata_fields;
+use crate::events::WhitelistEvent;
+use crate::pause::EPauseScope;
+use crate::{Contract, ContractExt, EProposalKind, ProjectMetadata};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, serde_json, AccountId};
+use std::collections::HashSet;
+
+// every amendment keeps a copy of the previous version in the proposal, so their number is bounded
+pub const MAX_AMENDMENTS: usize = 10;
+
+// The proposal as it was before an amendment, with the votes the amendment cleared.
+#[near(serializers=[borsh, json])]
+pub struct ProposalAmendment {
+    contract_ids: HashSet<AccountId>,
+    metadata: String,
+    votes: HashSet<AccountId>,
+    rejections: HashSet<AccountId>,
+    amended_at: U64,
+}
+
+#[near]
+impl Contract {
+    // The proposer can change the contract ids or the metadata of a pending NEW or UPDATE proposal.
+    // Votes start over, and the proposal is checked again against the current state of the project.
+    pub fn amend_proposal(
+        &mut self,
+        proposal_id: String,
+        contract_ids: Option<HashSet<AccountId>>,
+        metadata: Option<String>,
+    ) -> bool {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        let proposal = self.proposals.get(&proposal_id).expect("Proposal not found");
+        if proposal.proposed_by != env::predecessor_account_id() {
+            panic!("Only the proposer can amend the proposal.");
+        }
+        if proposal.is_expired(self.config.proposal_ttl.0) {
+            panic!("Proposal has expired.");
+        }
+        proposal.assert_open_for_voting();
+        if proposal.kind != EProposalKind::NEW && proposal.kind != EProposalKind::UPDATE {
+            panic!("Only NEW and UPDATE proposals can be amended.");
+        }
+        if contract_ids.is_none() && metadata.is_none() {
+            panic!("Nothing to amend.");
+        }
+        if proposal.amendments.len() >= MAX_AMENDMENTS {
+            panic!("Proposal can be amended at most {} times.", MAX_AMENDMENTS);
+        }
+        if let Some(metadata) = &metadata {
+            serde_json::from_str::<ProjectMetadata>(metadata).expect("Incorrect metadata structure");
+        }
+
+        let mut proposal = self.proposals.remove(&proposal_id).unwrap();
+        self.unindex_proposal(&proposal_id, &proposal);
+
+        let previous = ProposalAmendment {
+            contract_ids: proposal.project_info.contract_ids.clone(),
+            metadata: proposal.project_info.metadata.clone(),
+            votes: std::mem::take(&mut proposal.votes),
+            rejections: std::mem::take(&mut proposal.rejections),
+            amended_at: U64(env::block_timestamp()),
+        };
+        // the delegates of the cleared votes are dropped with them
+        proposal.delegated_votes.clear();
+        if let Some(contract_ids) = contract_ids {
+            proposal.project_info.contract_ids = contract_ids;
+        }
+        if let Some(metadata) = metadata {
+            proposal.project_info.metadata = metadata;
+        }
+        self.assert_no_conflicts(&proposal);
+
+        let mut contracts_added: Vec<AccountId> = proposal
+            .project_info
+            .contract_ids
+            .difference(&previous.contract_ids)
+            .cloned()
+            .collect();
+        let mut contracts_removed: Vec<AccountId> = previous
+            .contract_ids
+            .difference(&proposal.project_info.contract_ids)
+            .cloned()
+            .collect();
+        contracts_added.sort();
+        contracts_removed.sort();
+        let metadata_fields_changed =
+            changed_metadata_fields(&previous.metadata, &proposal.project_info.metadata);
+
+        // the amended proposal is based on the current version of the project
+        proposal.base_versions.clear();
+        proposal.stale = false;
+        proposal.amendments.push(previous);
+        let amendment_count = proposal.amendments.len() as u32;
+        self.insert_proposal(proposal_id.clone(), proposal);
+
+        WhitelistEvent::ProposalAmended {
+            proposal_id,
+            amended_by: env::predecessor_account_id(),
+            amendment_count,
+            contracts_added,
+            contracts_removed,
+            metadata_fields_changed,
+        }
+        .emit();
+        true
+    }
+}
@@ -0,0 +1,57 @@
+use crate::guardians::EVoteAction;
+use crate::{Contract, ContractExt, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, AccountId};
+
+pub const MAX_COMMENT_LENGTH: usize = 500;
+// comments are stored with the proposal, so their number is bounded like their length
+pub const MAX_COMMENTS_PER_PROPOSAL: usize = 50;
+
+#[near(serializers=[borsh, json])]
+pub struct ProposalComment {
+    account_id: AccountId,
+    // vote the comment was attached to, null for a plain comment
+    action: Option<EVoteAction>,
+    comment: String,
+    created_at: U64,
+}
+
+impl ProposalInfo {
+    pub(crate) fn add_comment(&mut self, action: Option<EVoteAction>, comment: Option<String>) {
+        let Some(comment) = comment else {
+            return;
+        };
+        if comment.is_empty() {
+            panic!("Comment must not be empty");
+        }
+        if comment.chars().count() > MAX_COMMENT_LENGTH {
+            panic!("Comment must be at most {} characters", MAX_COMMENT_LENGTH);
+        }
+        if self.comments.len() >= MAX_COMMENTS_PER_PROPOSAL {
+            panic!("Proposal has reached the limit of {} comments", MAX_COMMENTS_PER_PROPOSAL);
+        }
+
+        self.comments.push(ProposalComment {
+            account_id: env::predecessor_account_id(),
+            action,
+            comment,
+            created_at: U64(env::block_timestamp()),
+        });
+    }
+}
+
+#[near]
+impl Contract {
+    // Guardians and the proposer can discuss a pending proposal without voting.
+    pub fn comment_proposal(&mut self, proposal_id: String, comment: String) -> bool {
+        let account_id = env::predecessor_account_id();
+        let is_one_of_guardians = self.guardians.contains(&account_id);
+        let proposal = self.proposals.get_mut(&proposal_id).expect("Proposal not found");
+        if !is_one_of_guardians && proposal.proposed_by != account_id {
+            panic!("Only guardians and the proposer can comment the proposal.");
+        }
+
+        proposal.add_comment(None, Some(comment));
+        true
+    }
+}
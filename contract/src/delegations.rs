@@ -0,0 +1,153 @@
+use crate::{Contract, ContractExt, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, AccountId};
+
+// 30 days in nanoseconds
+pub const MAX_DELEGATION_DURATION: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+#[near(serializers=[borsh, json])]
+pub struct VoteDelegation {
+    // another guardian or a hot key account
+    delegate: AccountId,
+    from: U64,
+    until: U64,
+}
+
+impl VoteDelegation {
+    fn is_active_for(&self, account_id: &AccountId) -> bool {
+        &self.delegate == account_id && env::block_timestamp() < self.until.0
+    }
+}
+
+impl ProposalInfo {
+    fn has_voted_in_person(&self, guardian: &AccountId) -> bool {
+        self.has_voted(guardian) && !self.delegated_votes.contains_key(guardian)
+    }
+
+    // Records who cast the vote of the guardian, when it was not the guardian.
+    pub(crate) fn attribute_vote(&mut self, guardian: &AccountId) {
+        let caller = env::predecessor_account_id();
+        if guardian == &caller {
+            self.delegated_votes.remove(guardian);
+        } else {
+            self.delegated_votes.insert(guardian.clone(), caller);
+        }
+    }
+
+    // Drops every vote cast by or on behalf of the guardian.
+    pub(crate) fn remove_guardian_votes(&mut self, guardian: &AccountId) {
+        let represented: Vec<AccountId> = self
+            .delegated_votes
+            .iter()
+            .filter(|(_, delegate)| *delegate == guardian)
+            .map(|(represented, _)| represented.clone())
+            .chain([guardian.clone()])
+            .collect();
+
+        for account_id in represented {
+            self.votes.remove(&account_id);
+            self.rejections.remove(&account_id);
+            self.delegated_votes.remove(&account_id);
+        }
+    }
+}
+
+impl Contract {
+    // Guardians the caller votes for on the proposal: themselves when they are a guardian,
+    // and every guardian delegating to them who did not vote in person.
+    pub(crate) fn voting_guardians(&self, proposal: &ProposalInfo) -> Vec<AccountId> {
+        let caller = env::predecessor_account_id();
+        let mut voters: Vec<AccountId> = self
+            .guardians
+            .iter()
+            .filter(|guardian| {
+                self.vote_delegations
+                    .get(*guardian)
+                    .is_some_and(|delegation| delegation.is_active_for(&caller))
+                    && !proposal.has_voted_in_person(guardian)
+            })
+            .cloned()
+            .collect();
+        if self.guardians.contains(&caller) {
+            voters.push(caller);
+        }
+
+        if voters.is_empty() {
+            panic!("You are not a qualified guardian");
+        }
+        voters
+    }
+
+    // Delegations granted by or to the guardian end with their guardian role.
+    pub(crate) fn remove_guardian_delegations(&mut self, guardian: &AccountId) {
+        self.vote_delegations.remove(guardian);
+
+        let delegating: Vec<AccountId> = self
+            .guardians
+            .iter()
+            .filter(|delegator| {
+                self.vote_delegations
+                    .get(*delegator)
+                    .is_some_and(|delegation| &delegation.delegate == guardian)
+            })
+            .cloned()
+            .collect();
+        for delegator in delegating {
+            self.vote_delegations.remove(&delegator);
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // Lets another account vote on behalf of the calling guardian for a bounded period.
+    // A new delegation replaces the previous one.
+    pub fn delegate_vote(&mut self, delegate: AccountId, duration: U64) -> bool {
+        let guardian = env::predecessor_account_id();
+        if !self.guardians.contains(&guardian) {
+            panic!("You are not a qualified guardian");
+        }
+        if delegate == guardian {
+            panic!("Cannot delegate to yourself");
+        }
+        if duration.0 == 0 || duration.0 > MAX_DELEGATION_DURATION {
+            panic!("Delegation duration must be between 1 and {} nanoseconds", MAX_DELEGATION_DURATION);
+        }
+
+        let now = env::block_timestamp();
+        self.vote_delegations.insert(
+            guardian,
+            VoteDelegation {
+                delegate,
+                from: U64(now),
+                until: U64(now + duration.0),
+            },
+        );
+        true
+    }
+
+    // Votes already cast by the delegate are kept.
+    pub fn revoke_vote_delegation(&mut self) -> bool {
+        if self.vote_delegations.remove(&env::predecessor_account_id()).is_none() {
+            panic!("You have no delegation");
+        }
+        true
+    }
+
+    pub fn get_vote_delegation(&self, account_id: AccountId) -> Option<&VoteDelegation> {
+        self.vote_delegations.get(&account_id)
+    }
+
+    // Delegations of the current guardians that did not end yet.
+    pub fn list_vote_delegations(&self) -> Vec<(&AccountId, &VoteDelegation)> {
+        let now = env::block_timestamp();
+        let mut delegations: Vec<(&AccountId, &VoteDelegation)> = self
+            .guardians
+            .iter()
+            .filter_map(|guardian| Some((guardian, self.vote_delegations.get(guardian)?)))
+            .filter(|(_, delegation)| now < delegation.until.0)
+            .collect();
+        delegations.sort_by_key(|(guardian, _)| *guardian);
+        delegations
+    }
+}
@@ -0,0 +1,189 @@
+use crate::pagination::{self, Page};
+use crate::pause::EPauseScope;
+use crate::{Contract, ContractExt, EProposalKind, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, log, near, AccountId};
+use std::collections::BTreeSet;
+
+// shared with integrators through the interface crate
+pub use near_whitelist_ext::EVerdict;
+
+pub const MAX_EVIDENCE_URLS: usize = 10;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EDenylistReason {
+    PHISHING,
+    DRAINER,
+    RUGPULL,
+    IMPERSONATION,
+    EXPLOIT,
+    OTHER,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct DenylistEvidence {
+    reason: EDenylistReason,
+    evidence_urls: Vec<String>,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct DenylistChange {
+    contract_ids: BTreeSet<AccountId>,
+    // set when kind is DENYLIST
+    evidence: Option<DenylistEvidence>,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct DenylistEntry {
+    reason: EDenylistReason,
+    evidence_urls: Vec<String>,
+    // the DENYLIST proposal that added the contract
+    proposal_id: String,
+    denylisted_at: U64,
+}
+
+impl Contract {
+    pub(crate) fn is_denylisted(&self, contract_id: &AccountId) -> bool {
+        self.denylist.contains_key(contract_id)
+    }
+
+    pub(crate) fn contract_verdict(&self, contract_id: &AccountId) -> EVerdict {
+        if self.is_denylisted(contract_id) {
+            EVerdict::MALICIOUS
+        } else if self
+            .contract_project_index
+            .get(contract_id)
+            .is_some_and(|project_id| !self.is_project_suspended(project_id))
+        {
+            EVerdict::TRUSTED
+        } else {
+            EVerdict::UNKNOWN
+        }
+    }
+
+    fn insert_denylist_proposal(&mut self, kind: EProposalKind, change: DenylistChange) -> String {
+        let mut proposal = ProposalInfo::without_project(kind);
+        proposal.denylist = Some(change);
+
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+
+    pub(crate) fn finalize_denylist(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        let change = proposal
+            .denylist
+            .clone()
+            .expect("Proposal is missing the denylist change");
+
+        match change.evidence {
+            Some(evidence) => {
+                for contract_id in change.contract_ids {
+                    log!("{} is denylisted: {:?}", contract_id, evidence.reason);
+                    self.denylist.insert(
+                        contract_id,
+                        DenylistEntry {
+                            reason: evidence.reason,
+                            evidence_urls: evidence.evidence_urls.clone(),
+                            proposal_id: proposal_id.to_string(),
+                            denylisted_at: U64(env::block_timestamp()),
+                        },
+                    );
+                }
+            }
+            None => {
+                for contract_id in change.contract_ids {
+                    if self.denylist.remove(&contract_id).is_some() {
+                        log!("{} is removed from the denylist", contract_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // Proposes denylisting contracts as known malicious, with at least one evidence link.
+    #[payable]
+    pub fn propose_denylist(
+        &mut self,
+        contract_ids: BTreeSet<AccountId>,
+        reason: EDenylistReason,
+        evidence_urls: Vec<String>,
+    ) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        if contract_ids.is_empty() {
+            panic!("No contract to denylist");
+        }
+        if evidence_urls.is_empty() || evidence_urls.len() > MAX_EVIDENCE_URLS {
+            panic!("Between 1 and {} evidence links are required", MAX_EVIDENCE_URLS);
+        }
+        if evidence_urls.iter().any(|url| url.is_empty()) {
+            panic!("Evidence links can't be empty");
+        }
+        for contract_id in contract_ids.iter() {
+            if self.is_denylisted(contract_id) {
+                panic!("{} is denylisted already", contract_id);
+            }
+        }
+
+        self.insert_denylist_proposal(
+            EProposalKind::DENYLIST,
+            DenylistChange {
+                contract_ids,
+                evidence: Some(DenylistEvidence { reason, evidence_urls }),
+            },
+        )
+    }
+
+    #[payable]
+    pub fn propose_undenylist(&mut self, contract_ids: BTreeSet<AccountId>) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        if contract_ids.is_empty() {
+            panic!("No contract to remove from the denylist");
+        }
+        for contract_id in contract_ids.iter() {
+            if !self.is_denylisted(contract_id) {
+                panic!("{} is not denylisted", contract_id);
+            }
+        }
+
+        self.insert_denylist_proposal(
+            EProposalKind::UNDENYLIST,
+            DenylistChange {
+                contract_ids,
+                evidence: None,
+            },
+        )
+    }
+
+    pub fn get_denylist_entry(&self, contract_id: AccountId) -> Option<&DenylistEntry> {
+        self.denylist.get(&contract_id)
+    }
+
+    pub fn list_denylist(
+        &self,
+        cursor: Option<AccountId>,
+        limit: Option<u32>,
+    ) -> Page<AccountId, (&AccountId, &DenylistEntry)> {
+        Page::collect(
+            self.denylist.range(pagination::after(cursor)),
+            limit,
+            self.denylist.len(),
+            |(contract_id, _)| (*contract_id).clone(),
+        )
+    }
+
+    pub fn get_contract_verdict(&self, contract_id: AccountId) -> EVerdict {
+        self.contract_verdict(&contract_id)
+    }
+}
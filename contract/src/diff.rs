@@ -0,0 +1,147 @@
+use crate::{Contract, ContractExt, EProposalKind, ProjectInfo, ProposalInfo};
+use near_sdk::serde_json::{self, Map, Value};
+use near_sdk::{near, AccountId};
+use std::collections::{BTreeSet, HashSet};
+
+#[near(serializers=[json])]
+pub struct ContractConflict {
+    contract_id: AccountId,
+    // project currently owning the contract
+    project_id: String,
+}
+
+// Changes a proposal would make to one project if it was executed now.
+#[near(serializers=[json])]
+pub struct ProjectDiff {
+    project_id: String,
+    contracts_added: Vec<AccountId>,
+    contracts_removed: Vec<AccountId>,
+    // added contracts owned by a project the proposal does not change, they would be skipped
+    contracts_conflicting: Vec<ContractConflict>,
+    metadata_fields_changed: Vec<String>,
+}
+
+fn metadata_fields(metadata: &str) -> Map<String, Value> {
+    serde_json::from_str(metadata).unwrap_or_default()
+}
+
+pub(crate) fn changed_metadata_fields(current: &str, proposed: &str) -> Vec<String> {
+    let (current, proposed) = (metadata_fields(current), metadata_fields(proposed));
+    let keys: BTreeSet<&String> = current.keys().chain(proposed.keys()).collect();
+
+    // a missing field and a null field are the same
+    keys.into_iter()
+        .filter(|key| {
+            current.get(*key).unwrap_or(&Value::Null) != proposed.get(*key).unwrap_or(&Value::Null)
+        })
+        .cloned()
+        .collect()
+}
+
+impl Contract {
+    fn project_diff(
+        &self,
+        current_project_id: &str,
+        proposed: &ProjectInfo,
+        affected_project_ids: &[&str],
+    ) -> ProjectDiff {
+        let empty_contract_ids = HashSet::new();
+        let (current_contract_ids, current_metadata) = match self.approved_projects.get(current_project_id) {
+            Some(project) => (&project.contract_ids, project.metadata.as_str()),
+            None => (&empty_contract_ids, ""),
+        };
+
+        let mut contracts_added: Vec<AccountId> =
+            proposed.contract_ids.difference(current_contract_ids).cloned().collect();
+        let mut contracts_removed: Vec<AccountId> =
+            current_contract_ids.difference(&proposed.contract_ids).cloned().collect();
+        contracts_added.sort();
+        contracts_removed.sort();
+
+        let contracts_conflicting = contracts_added
+            .iter()
+            .filter_map(|contract_id| {
+                let project_id = self.contract_project_index.get(contract_id)?;
+                (!affected_project_ids.contains(&project_id.as_str())).then(|| ContractConflict {
+                    contract_id: contract_id.clone(),
+                    project_id: project_id.clone(),
+                })
+            })
+            .collect();
+
+        ProjectDiff {
+            project_id: proposed.project_id.clone(),
+            contracts_added,
+            contracts_removed,
+            contracts_conflicting,
+            metadata_fields_changed: changed_metadata_fields(current_metadata, &proposed.metadata),
+        }
+    }
+
+    // One diff per project the proposal changes, the target project first.
+    pub(crate) fn proposal_diff(&self, proposal: &ProposalInfo) -> Vec<ProjectDiff> {
+        let affected_project_ids = proposal.affected_project_ids();
+        if affected_project_ids.is_empty() {
+            return Vec::new();
+        }
+        // a NEW proposal creates its project, so there is nothing to compare with
+        let current_project_id = match proposal.kind {
+            EProposalKind::NEW => "",
+            _ => proposal.target_project_id(),
+        };
+
+        let mut diffs = vec![self.project_diff(
+            current_project_id,
+            &proposal.project_info,
+            &affected_project_ids,
+        )];
+        if let Some(related_project) = &proposal.related_project {
+            diffs.push(self.project_diff(
+                &related_project.project_id,
+                related_project,
+                &affected_project_ids,
+            ));
+        }
+        diffs
+    }
+
+    pub(crate) fn assert_no_conflicts(&self, proposal: &ProposalInfo) {
+        for diff in self.proposal_diff(proposal) {
+            if let Some(conflict) = diff.contracts_conflicting.first() {
+                panic!(
+                    "{} is associated with project {} already.",
+                    conflict.contract_id, conflict.project_id
+                )
+            }
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // Dry run of a pending proposal against the current state.
+    pub fn get_proposal_diff(&self, proposal_id: String) -> Option<Vec<ProjectDiff>> {
+        self.proposals
+            .get(&proposal_id)
+            .map(|proposal| self.proposal_diff(proposal))
+    }
+
+    // Dry run of update_project, without submitting the proposal.
+    pub fn preview_update_project(
+        &self,
+        project_id: String,
+        contract_ids: HashSet<AccountId>,
+        metadata: String,
+    ) -> Option<ProjectDiff> {
+        if !self.approved_projects.contains_key(&project_id) {
+            return None;
+        }
+        let proposed = ProjectInfo {
+            contract_ids,
+            metadata,
+            pending_proposals: HashSet::new(),
+            project_id: project_id.clone(),
+        };
+        Some(self.project_diff(&project_id, &proposed, &[&project_id]))
+    }
+}
@@ -0,0 +1,16 @@
+use near_sdk::{near, AccountId};
+
+// NEP-297 events, emitted as `EVENT_JSON:` logs for indexers and guardian tooling.
+#[near(event_json(standard = "whitelist"))]
+pub enum WhitelistEvent {
+    #[event_version("1.0.0")]
+    ProposalAmended {
+        proposal_id: String,
+        amended_by: AccountId,
+        // number of amendments so far, including this one
+        amendment_count: u32,
+        contracts_added: Vec<AccountId>,
+        contracts_removed: Vec<AccountId>,
+        metadata_fields_changed: Vec<String>,
+    },
+}
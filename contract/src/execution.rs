@@ -0,0 +1,283 @@
+use crate::{Contract, EProposalKind, EProposalStatus, ProjectInfo, ProposalInfo};
+use near_sdk::{log, near, AccountId};
+use std::collections::HashSet;
+
+// contract index writes done by a single execute_proposal call
+pub const MAX_CONTRACT_MOVES_PER_CALL: u32 = 100;
+
+// One write to contract_project_index. It is skipped when the contract is not
+// owned by `from` anymore, null meaning not whitelisted.
+#[near(serializers=[borsh, json])]
+pub struct ContractMove {
+    contract_id: AccountId,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct ExecutionProgress {
+    moves: Vec<ContractMove>,
+    // number of moves done so far
+    done: u32,
+}
+
+fn moves_between(
+    contract_ids: impl Iterator<Item = AccountId>,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Vec<ContractMove> {
+    let mut contract_ids: Vec<AccountId> = contract_ids.collect();
+    contract_ids.sort();
+    contract_ids
+        .into_iter()
+        .map(|contract_id| ContractMove {
+            contract_id,
+            from: from.map(String::from),
+            to: to.map(String::from),
+        })
+        .collect()
+}
+
+impl Contract {
+    // Index writes needed by the proposal, based on the current state.
+    // Project records are only changed once every move is done, see finalize_execution.
+    fn plan_contract_moves(&self, proposal: &ProposalInfo) -> Vec<ContractMove> {
+        let target_project_id = proposal.target_project_id();
+        let project_contract_ids = |project_id: &str| {
+            self.approved_projects
+                .get(project_id)
+                .map(|project| project.contract_ids.clone())
+                .unwrap_or_default()
+        };
+
+        match proposal.kind {
+            EProposalKind::NEW => {
+                let project_id = &proposal.project_info.project_id;
+                if self.is_project_id_taken(project_id) {
+                    return Vec::new();
+                }
+                moves_between(proposal.project_info.contract_ids.iter().cloned(), None, Some(project_id))
+            }
+            EProposalKind::UPDATE => {
+                if !self.approved_projects.contains_key(target_project_id) {
+                    return Vec::new();
+                }
+                let existing_contract_ids = project_contract_ids(target_project_id);
+                let new_contract_ids = &proposal.project_info.contract_ids;
+                let mut moves = moves_between(
+                    existing_contract_ids.difference(new_contract_ids).cloned(),
+                    Some(target_project_id),
+                    None,
+                );
+                moves.extend(moves_between(
+                    new_contract_ids.difference(&existing_contract_ids).cloned(),
+                    None,
+                    Some(target_project_id),
+                ));
+                moves
+            }
+            EProposalKind::TRANSFER | EProposalKind::MERGE | EProposalKind::SPLIT => {
+                let related_project = proposal
+                    .related_project
+                    .as_ref()
+                    .expect("Proposal is missing the related project");
+                let is_related_project_ready = match proposal.kind {
+                    EProposalKind::SPLIT => !self.is_project_id_taken(&related_project.project_id),
+                    _ => self.approved_projects.contains_key(&related_project.project_id),
+                };
+                if !self.approved_projects.contains_key(target_project_id) || !is_related_project_ready {
+                    return Vec::new();
+                }
+
+                let source_contract_ids = project_contract_ids(target_project_id);
+                let moved_contract_ids: HashSet<AccountId> = match proposal.kind {
+                    // every contract of the retired project, including the ones added since the proposal
+                    EProposalKind::MERGE => source_contract_ids,
+                    _ => source_contract_ids
+                        .intersection(&related_project.contract_ids)
+                        .cloned()
+                        .collect(),
+                };
+                moves_between(
+                    moved_contract_ids.into_iter(),
+                    Some(target_project_id),
+                    Some(&related_project.project_id),
+                )
+            }
+            EProposalKind::RENAME => {
+                let new_project_id = &proposal.project_info.project_id;
+                if !self.approved_projects.contains_key(target_project_id)
+                    || self.is_project_id_taken(new_project_id)
+                {
+                    return Vec::new();
+                }
+                moves_between(
+                    project_contract_ids(target_project_id).into_iter(),
+                    Some(target_project_id),
+                    Some(new_project_id),
+                )
+            }
+            EProposalKind::REMOVE => {
+                if !self.approved_projects.contains_key(target_project_id) {
+                    return Vec::new();
+                }
+                moves_between(
+                    project_contract_ids(target_project_id).into_iter(),
+                    Some(target_project_id),
+                    None,
+                )
+            }
+            EProposalKind::UNPAUSE
+            | EProposalKind::UPGRADE
+            | EProposalKind::DENYLIST
+            | EProposalKind::UNDENYLIST => Vec::new(),
+        }
+    }
+
+    fn move_contract(&mut self, contract_move: &ContractMove) {
+        let current_project_id = self.contract_project_index.get(&contract_move.contract_id);
+        if current_project_id != contract_move.from.as_ref() {
+            match current_project_id {
+                Some(associated_project_id) => {
+                    log!("Skipping {}, as it is paired with {}", contract_move.contract_id, associated_project_id);
+                }
+                None => {
+                    log!("Skipping {}, as it is not whitelisted anymore", contract_move.contract_id);
+                }
+            }
+            return;
+        }
+
+        if contract_move.from.is_some() {
+            self.unindex_contract(&contract_move.contract_id);
+        }
+        if let Some(to) = &contract_move.to {
+            self.index_contract(&contract_move.contract_id, to);
+        }
+    }
+
+    // Runs the next chunk of the proposal's execution, returns whether it is finished.
+    // Only one proposal is executed at a time, whatever its kind, so the planned moves stay
+    // valid between chunks. UNPAUSE proposals are the exception, like they are for the pause:
+    // a paused execution could otherwise never be resumed.
+    pub(crate) fn execute_chunk(&mut self, proposal_id: &str) -> bool {
+        let is_unpause = self
+            .proposals
+            .get(proposal_id)
+            .is_some_and(|proposal| proposal.kind == EProposalKind::UNPAUSE);
+        if let Some(executing_proposal_id) = &self.executing_proposal {
+            if executing_proposal_id != proposal_id && !is_unpause {
+                panic!("Proposal {} is being executed, it must finish first.", executing_proposal_id);
+            }
+        }
+
+        // proposals that don't touch projects are executed in one call
+        if self.proposals.get(proposal_id).is_some_and(|proposal| !proposal.is_project_proposal()) {
+            self.finish_execution(proposal_id);
+            return true;
+        }
+
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        if proposal.execution.is_none() {
+            if proposal.stale {
+                panic!("Proposal is stale, the project changed since it was submitted.");
+            }
+            let moves = self.plan_contract_moves(proposal);
+            self.proposals.get_mut(proposal_id).unwrap().execution =
+                Some(ExecutionProgress { moves, done: 0 });
+            self.executing_proposal = Some(proposal_id.to_string());
+        }
+
+        let mut progress = self
+            .proposals
+            .get_mut(proposal_id)
+            .unwrap()
+            .execution
+            .take()
+            .unwrap();
+        let end = progress
+            .moves
+            .len()
+            .min(progress.done as usize + MAX_CONTRACT_MOVES_PER_CALL as usize);
+        for contract_move in progress.moves[progress.done as usize..end].iter() {
+            self.move_contract(contract_move);
+        }
+        progress.done = end as u32;
+
+        let remaining = progress.moves.len() - end;
+        self.proposals.get_mut(proposal_id).unwrap().execution = Some(progress);
+        if remaining > 0 {
+            log!("{} contracts left to execute proposal {}", remaining, proposal_id);
+            return false;
+        }
+
+        self.executing_proposal = None;
+        self.finish_execution(proposal_id);
+        true
+    }
+
+    fn finish_execution(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        Contract::refund_deposit(proposal);
+        self.finalize_execution(proposal_id);
+        self.archive_proposal(proposal_id, EProposalStatus::APPROVED);
+        self.flag_stale_proposals(proposal_id);
+    }
+
+    // Updates the project records once every contract move of the proposal is done.
+    fn finalize_execution(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        match proposal.kind {
+            EProposalKind::NEW => self.finalize_new(proposal_id),
+            EProposalKind::UPDATE => self.finalize_update(proposal_id),
+            EProposalKind::TRANSFER => self.finalize_transfer(proposal_id),
+            EProposalKind::MERGE => self.finalize_merge(proposal_id),
+            EProposalKind::SPLIT => self.finalize_split(proposal_id),
+            EProposalKind::RENAME => self.finalize_rename(proposal_id),
+            EProposalKind::REMOVE => self.finalize_remove(proposal_id),
+            EProposalKind::UNPAUSE => self.finalize_unpause(proposal_id),
+            EProposalKind::UPGRADE => self.finalize_upgrade(proposal_id),
+            EProposalKind::DENYLIST | EProposalKind::UNDENYLIST => self.finalize_denylist(proposal_id),
+        }
+    }
+
+    fn proposed_project(&self, proposal_id: &str) -> ProjectInfo {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        ProjectInfo {
+            contract_ids: proposal.project_info.contract_ids.clone(),
+            metadata: proposal.project_info.metadata.clone(),
+            pending_proposals: HashSet::new(),
+            project_id: proposal.project_info.project_id.clone(),
+        }
+    }
+
+    fn finalize_new(&mut self, proposal_id: &str) {
+        let mut project_info = self.proposed_project(proposal_id);
+        let project_id = project_info.project_id.clone();
+        if self.is_project_id_taken(&project_id) {
+            log!("Skipping add project as the project id is occupied.");
+            return;
+        }
+
+        project_info.pending_proposals = self.pending_proposal_ids(&project_id);
+        project_info.pending_proposals.remove(proposal_id);
+        self.approved_projects.insert(project_id.clone(), project_info);
+        self.record_project_version(&project_id, Some(proposal_id.to_string()));
+    }
+
+    fn finalize_update(&mut self, proposal_id: &str) {
+        let mut project_info = self.proposed_project(proposal_id);
+        let project_id = project_info.project_id.clone();
+        match self.approved_projects.get(&project_id) {
+            None => {
+                log!("Project not found for the proposal, and no update will be performed")
+            }
+            Some(existing_project) => {
+                project_info.pending_proposals = existing_project.pending_proposals.clone();
+                project_info.pending_proposals.remove(proposal_id);
+                self.approved_projects.insert(project_id.clone(), project_info);
+                self.record_project_version(&project_id, Some(proposal_id.to_string()));
+            }
+        }
+    }
+}
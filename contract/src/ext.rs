@@ -0,0 +1,18 @@
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, AccountId};
+use near_whitelist_ext::VerdictResponse;
+
+#[near]
+impl Contract {
+    // Meant to be called by other contracts through near_whitelist_ext::ext_whitelist,
+    // the verdict is returned as the promise result.
+    pub fn query_verdict(&self, account_id: AccountId) -> VerdictResponse {
+        VerdictResponse {
+            verdict: self.contract_verdict(&account_id),
+            project_id: self.contract_project_index.get(&account_id).cloned(),
+            checked_at: U64(env::block_timestamp()),
+            account_id,
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::pagination::Page;
+use crate::{Contract, ContractExt, EProposalStatus, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, near, AccountId};
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy)]
+pub enum EVoteAction {
+    APPROVE,
+    REJECT,
+    WITHDRAW,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct GuardianVote {
+    // position in the guardian's history, starting from 1
+    index: u32,
+    proposal_id: String,
+    action: EVoteAction,
+    voted_at: U64,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Default)]
+pub struct GuardianStats {
+    approvals: u32,
+    rejections: u32,
+    withdrawals: u32,
+    last_voted_at: Option<U64>,
+    // proposals approved, rejected or expired while being a guardian
+    finalized_proposals: u32,
+    // the part of finalized_proposals the guardian had voted on
+    participated_proposals: u32,
+}
+
+impl GuardianStats {
+    fn vote_count(&self) -> u32 {
+        self.approvals + self.rejections + self.withdrawals
+    }
+}
+
+#[near(serializers=[json])]
+pub struct GuardianParticipation {
+    account_id: AccountId,
+    is_guardian: bool,
+    stats: Option<GuardianStats>,
+    // pending proposals the guardian has not voted on yet
+    awaiting_votes: u32,
+}
+
+impl ProposalInfo {
+    pub(crate) fn has_voted(&self, account_id: &AccountId) -> bool {
+        self.votes.contains(account_id) || self.rejections.contains(account_id)
+    }
+}
+
+impl Contract {
+    // A vote cast by a delegate counts for each guardian it represents, not for the delegate.
+    pub(crate) fn record_guardian_vote(
+        &mut self,
+        voters: &[AccountId],
+        proposal_id: &str,
+        action: EVoteAction,
+    ) {
+        for voter in voters {
+            self.record_guardian_action(voter.clone(), proposal_id, action);
+        }
+    }
+
+    fn record_guardian_action(&mut self, account_id: AccountId, proposal_id: &str, action: EVoteAction) {
+        let voted_at = U64(env::block_timestamp());
+
+        let stats = self.guardian_stats.entry(account_id.clone()).or_default();
+        match action {
+            EVoteAction::APPROVE => stats.approvals += 1,
+            EVoteAction::REJECT => stats.rejections += 1,
+            EVoteAction::WITHDRAW => stats.withdrawals += 1,
+        }
+        stats.last_voted_at = Some(voted_at);
+        let index = stats.vote_count();
+
+        self.guardian_votes.insert(
+            (account_id, index),
+            GuardianVote {
+                index,
+                proposal_id: proposal_id.to_string(),
+                action,
+                voted_at,
+            },
+        );
+    }
+
+    // Called when a proposal is finalized, before it leaves the pending proposals.
+    pub(crate) fn record_guardian_participation(&mut self, proposal: &ProposalInfo) {
+        if proposal.status == EProposalStatus::CANCELLED {
+            return;
+        }
+
+        for guardian in self.guardians.iter() {
+            let stats = self.guardian_stats.entry(guardian.clone()).or_default();
+            stats.finalized_proposals += 1;
+            if proposal.has_voted(guardian) {
+                stats.participated_proposals += 1;
+            }
+        }
+    }
+
+    fn proposals_awaiting(
+        &self,
+        account_id: AccountId,
+        cursor: Option<String>,
+    ) -> impl Iterator<Item = (&String, &ProposalInfo)> {
+        self.newest_proposals(cursor)
+            .filter(move |(_, proposal)| {
+                proposal.status == EProposalStatus::PENDING && !proposal.has_voted(&account_id)
+            })
+    }
+
+    fn guardian_participation(&self, account_id: AccountId) -> GuardianParticipation {
+        GuardianParticipation {
+            is_guardian: self.guardians.contains(&account_id),
+            stats: self.guardian_stats.get(&account_id).cloned(),
+            awaiting_votes: self.proposals_awaiting(account_id.clone(), None).count() as u32,
+            account_id,
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // Pending proposals the guardian has neither voted for nor rejected.
+    // The page has no total, get_guardian_participation returns the count.
+    pub fn list_proposals_awaiting_vote(
+        &self,
+        account_id: AccountId,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProposalInfo)> {
+        Page::collect_untotaled(
+            self.proposals_awaiting(account_id, cursor),
+            limit,
+            |(proposal_id, _)| (*proposal_id).clone(),
+        )
+    }
+
+    pub fn list_guardian_votes(
+        &self,
+        account_id: AccountId,
+        cursor: Option<u32>,
+        limit: Option<u32>,
+    ) -> Page<u32, &GuardianVote> {
+        let vote_count = self
+            .guardian_stats
+            .get(&account_id)
+            .map_or(0, |stats| stats.vote_count());
+        let from = cursor.map_or(1, |index| index.saturating_add(1));
+
+        Page::collect(
+            (from..=vote_count)
+                .filter_map(|index| self.guardian_votes.get(&(account_id.clone(), index))),
+            limit,
+            vote_count,
+            |vote| vote.index,
+        )
+    }
+
+    pub fn get_guardian_participation(&self, account_id: AccountId) -> GuardianParticipation {
+        self.guardian_participation(account_id)
+    }
+
+    // Participation of every current guardian.
+    pub fn list_guardian_participation(&self) -> Vec<GuardianParticipation> {
+        self.guardians
+            .iter()
+            .map(|guardian| self.guardian_participation(guardian.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,204 @@
+use crate::pagination::Page;
+use crate::{Contract, ContractExt, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, log, near, AccountId};
+use std::collections::HashSet;
+
+#[near(serializers=[borsh, json])]
+pub struct ProjectVersion {
+    version: u32,
+    contract_ids: HashSet<AccountId>,
+    metadata: String,
+    // proposal that produced this version, null for versions recorded during a migration or by a curator
+    proposal_id: Option<String>,
+    created_at: U64,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct WhitelistPeriod {
+    project_id: String,
+    from: U64,
+    // null while the contract is still whitelisted
+    until: Option<U64>,
+}
+
+impl WhitelistPeriod {
+    fn contains(&self, timestamp: u64) -> bool {
+        let is_before_until = match self.until {
+            Some(until) => timestamp < until.0,
+            None => true,
+        };
+        self.from.0 <= timestamp && is_before_until
+    }
+}
+
+impl Contract {
+    // Snapshots the current state of an approved project as its next version.
+    pub(crate) fn record_project_version(&mut self, project_id: &str, proposal_id: Option<String>) {
+        let project = self
+            .approved_projects
+            .get(project_id)
+            .expect("Project not found");
+        let version = self.project_version(project_id) + 1;
+
+        self.project_versions.insert(
+            (project_id.to_string(), version),
+            ProjectVersion {
+                version,
+                contract_ids: project.contract_ids.clone(),
+                metadata: project.metadata.clone(),
+                proposal_id,
+                created_at: U64(env::block_timestamp()),
+            },
+        );
+        self.project_version_counts.insert(project_id.to_string(), version);
+    }
+
+    pub(crate) fn project_version(&self, project_id: &str) -> u32 {
+        *self.project_version_counts.get(project_id).unwrap_or(&0)
+    }
+
+    fn is_outdated(&self, proposal: &ProposalInfo) -> bool {
+        proposal
+            .base_versions
+            .iter()
+            .any(|(project_id, version)| self.project_version(project_id) != *version)
+    }
+
+    // Flags the pending proposals built on a version of a project the approved proposal changed.
+    pub(crate) fn flag_stale_proposals(&mut self, approved_proposal_id: &str) {
+        let archived = self
+            .archived_proposals
+            .get(approved_proposal_id)
+            .expect("Proposal not found");
+        let project_ids = archived
+            .proposal
+            .affected_project_ids()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        self.flag_stale_project_proposals(project_ids);
+    }
+
+    pub(crate) fn flag_stale_project_proposals(&mut self, project_ids: Vec<String>) {
+        let pending_proposal_ids: Vec<String> = project_ids
+            .iter()
+            .flat_map(|project_id| self.pending_proposal_ids(project_id))
+            .collect();
+
+        for proposal_id in pending_proposal_ids {
+            let is_outdated = match self.proposals.get(&proposal_id) {
+                Some(proposal) => !proposal.stale && self.is_outdated(proposal),
+                None => false,
+            };
+            if is_outdated {
+                self.proposals.get_mut(&proposal_id).unwrap().stale = true;
+                log!("Proposal {} is stale", proposal_id);
+            }
+        }
+    }
+
+    // All writes to contract_project_index go through index_contract and unindex_contract,
+    // so that the whitelist periods of every contract and the Merkle root stay complete.
+    pub(crate) fn index_contract(&mut self, contract_id: &AccountId, project_id: &str) {
+        self.contract_project_index
+            .insert(contract_id.clone(), project_id.to_string());
+        self.merkle_insert(contract_id, project_id);
+
+        let now = env::block_timestamp();
+        let periods = self.contract_history.entry(contract_id.clone()).or_default();
+        match periods.last_mut() {
+            // removed and added back to the same project in one change, keep the period open
+            Some(last) if last.project_id == project_id && last.until == Some(U64(now)) => {
+                last.until = None;
+            }
+            _ => periods.push(WhitelistPeriod {
+                project_id: project_id.to_string(),
+                from: U64(now),
+                until: None,
+            }),
+        }
+    }
+
+    pub(crate) fn unindex_contract(&mut self, contract_id: &AccountId) {
+        if self.contract_project_index.remove(contract_id).is_some() {
+            self.merkle_remove(contract_id);
+            if let Some(last) = self
+                .contract_history
+                .get_mut(contract_id)
+                .and_then(|periods| periods.last_mut())
+            {
+                last.until = Some(U64(env::block_timestamp()));
+            }
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    pub fn list_project_versions(
+        &self,
+        project_id: String,
+        cursor: Option<u32>,
+        limit: Option<u32>,
+    ) -> Page<u32, &ProjectVersion> {
+        let version_count = self.project_version(&project_id);
+        let from = cursor.map_or(1, |version| version.saturating_add(1));
+
+        Page::collect(
+            (from..=version_count)
+                .filter_map(|version| self.project_versions.get(&(project_id.clone(), version))),
+            limit,
+            version_count,
+            |project_version| project_version.version,
+        )
+    }
+
+    pub fn get_project_version(&self, project_id: String, version: u32) -> Option<&ProjectVersion> {
+        self.project_versions.get(&(project_id, version))
+    }
+
+    // Returns the version of the project that was in effect at the given block timestamp.
+    pub fn get_project_version_at(&self, project_id: String, timestamp: U64) -> Option<&ProjectVersion> {
+        let version_count = self.project_version(&project_id);
+
+        // versions are created in chronological order, find the last one created at or before timestamp
+        let (mut low, mut high) = (1, version_count);
+        let mut found = None;
+        while low <= high {
+            let middle = low + (high - low) / 2;
+            let project_version = self.project_versions.get(&(project_id.clone(), middle))?;
+            if project_version.created_at.0 <= timestamp.0 {
+                found = Some(project_version);
+                low = middle + 1;
+            } else {
+                high = middle - 1;
+            }
+        }
+        found
+    }
+
+    pub fn list_contract_whitelist_periods(&self, contract_id: AccountId) -> Vec<&WhitelistPeriod> {
+        self.contract_history
+            .get(&contract_id)
+            .map(|periods| periods.iter().collect())
+            .unwrap_or_default()
+    }
+
+    // Returns the whitelist period covering the given block timestamp, null if the contract was not whitelisted then.
+    pub fn get_contract_whitelist_period_at(
+        &self,
+        contract_id: AccountId,
+        timestamp: U64,
+    ) -> Option<&WhitelistPeriod> {
+        self.contract_history
+            .get(&contract_id)?
+            .iter()
+            .find(|period| period.contains(timestamp.0))
+    }
+
+    pub fn was_contract_whitelisted_at(&self, contract_id: AccountId, timestamp: U64) -> bool {
+        self.get_contract_whitelist_period_at(contract_id, timestamp)
+            .is_some()
+    }
+}
@@ -0,0 +1,125 @@
+use crate::pagination::{self, Page};
+use crate::weights;
+use crate::{Contract, ContractExt, ProposalInfo};
+use near_sdk::{near, AccountId};
+use std::collections::{BTreeSet, HashSet};
+
+// Proposal ids are "{seconds}{running_id}" without padding, so sorting them as strings puts
+// "…10" before "…9". Both parts only grow, so the id read as a number follows the creation order.
+pub(crate) fn proposal_key(proposal_id: &str) -> u128 {
+    proposal_id.parse().expect("Invalid proposal id")
+}
+
+impl Contract {
+    // Stores a new pending proposal and registers it in the proposer and project indexes.
+    pub(crate) fn insert_proposal(&mut self, proposal_id: String, mut proposal: ProposalInfo) {
+        let key = proposal_key(&proposal_id);
+        self.proposal_timeline.insert(key, ());
+        self.proposals_by_proposer
+            .entry(proposal.proposed_by.clone())
+            .or_default()
+            .insert(key);
+
+        let affected_project_ids: Vec<String> =
+            proposal.affected_project_ids().into_iter().map(String::from).collect();
+        for project_id in affected_project_ids {
+            self.proposals_by_project
+                .entry(project_id.clone())
+                .or_default()
+                .insert(key);
+
+            if let Some(project) = self.approved_projects.get_mut(&project_id) {
+                project.pending_proposals.insert(proposal_id.clone());
+            }
+
+            let version = self.project_version(&project_id);
+            proposal.base_versions.entry(project_id).or_insert(version);
+        }
+
+        proposal.tally = weights::tally(&self.guardian_weights, &proposal);
+        self.proposals.insert(proposal_id, proposal);
+    }
+
+    pub(crate) fn unindex_proposal(&mut self, proposal_id: &str, proposal: &ProposalInfo) {
+        let key = proposal_key(proposal_id);
+        self.proposal_timeline.remove(&key);
+        if let Some(proposal_ids) = self.proposals_by_proposer.get_mut(&proposal.proposed_by) {
+            proposal_ids.remove(&key);
+            if proposal_ids.is_empty() {
+                self.proposals_by_proposer.remove(&proposal.proposed_by);
+            }
+        }
+
+        for project_id in proposal.affected_project_ids() {
+            if let Some(proposal_ids) = self.proposals_by_project.get_mut(project_id) {
+                proposal_ids.remove(&key);
+                if proposal_ids.is_empty() {
+                    self.proposals_by_project.remove(project_id);
+                }
+            }
+
+            if let Some(project) = self.approved_projects.get_mut(project_id) {
+                project.pending_proposals.remove(proposal_id);
+            }
+        }
+    }
+
+    pub(crate) fn pending_proposal_ids(&self, project_id: &str) -> HashSet<String> {
+        self.proposals_by_project
+            .get(project_id)
+            .map(|proposal_ids| proposal_ids.iter().map(u128::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    // Pending proposals created before the cursor, newest first.
+    pub(crate) fn newest_proposals(
+        &self,
+        cursor: Option<String>,
+    ) -> impl Iterator<Item = (&String, &ProposalInfo)> {
+        self.proposal_timeline
+            .range(pagination::before(cursor.as_deref().map(proposal_key)))
+            .rev()
+            .filter_map(|(key, _)| self.proposals.get_key_value(&key.to_string()))
+    }
+
+    fn paginate_proposal_ids(
+        &self,
+        proposal_ids: Option<&BTreeSet<u128>>,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProposalInfo)> {
+        let before = pagination::before(cursor.as_deref().map(proposal_key));
+        Page::collect(
+            proposal_ids
+                .into_iter()
+                .flat_map(|proposal_ids| proposal_ids.range(before).rev())
+                .filter_map(|key| self.proposals.get_key_value(&key.to_string())),
+            limit,
+            proposal_ids.map_or(0, |proposal_ids| proposal_ids.len() as u32),
+            |(proposal_id, _)| (*proposal_id).clone(),
+        )
+    }
+}
+
+#[near]
+impl Contract {
+    // Newest first, like every list of proposals.
+    pub fn list_proposals_by_proposer(
+        &self,
+        account_id: AccountId,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProposalInfo)> {
+        self.paginate_proposal_ids(self.proposals_by_proposer.get(&account_id), cursor, limit)
+    }
+
+    // NEW proposals are listed under the project id they propose.
+    pub fn list_proposals_by_project(
+        &self,
+        project_id: String,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProposalInfo)> {
+        self.paginate_proposal_ids(self.proposals_by_project.get(&project_id), cursor, limit)
+    }
+}
@@ -1,46 +1,141 @@
-use near_sdk::store::{IterableMap};
-use near_sdk::{env, log, near, AccountId, BorshStorageKey, NearToken, Promise};
-use std::collections::HashSet;
+// Enum variants are upper case on purpose, they are part of the JSON interface.
+#![allow(clippy::upper_case_acronyms)]
+
+use near_sdk::json_types::{Base58CryptoHash, U64};
+use near_sdk::store::{IterableMap, LookupMap, LookupSet, TreeMap};
+use near_sdk::{env, log, near, AccountId, BorshStorageKey, CryptoHash, NearToken, Promise};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use near_sdk::serde_json;
 
+pub mod amendments;
+pub mod comments;
+pub mod delegations;
+pub mod denylist;
+pub mod diff;
+pub mod execution;
+pub mod events;
+pub mod ext;
+pub mod guardians;
+pub mod history;
+pub mod indexes;
+pub mod merkle;
+pub mod ownership;
+pub mod pagination;
+pub mod pause;
+pub mod reports;
+pub mod restructure;
+pub mod roles;
+pub mod screening;
+pub mod suspensions;
+pub mod timelock;
+pub mod upgrade;
+pub mod weights;
+
+use amendments::ProposalAmendment;
+use comments::ProposalComment;
+use delegations::VoteDelegation;
+use denylist::{DenylistChange, DenylistEntry, EVerdict};
+use execution::ExecutionProgress;
+use guardians::{EVoteAction, GuardianStats, GuardianVote};
+use history::{ProjectVersion, WhitelistPeriod};
+use merkle::MerkleNode;
+use pagination::Page;
+use pause::{EPauseScope, PauseStatus};
+use reports::CommunityReport;
+use roles::ERole;
+use suspensions::ProjectSuspension;
+use upgrade::{CodeStaging, CodeUpgrade};
+use weights::ProposalTally;
+
+// Number of guardian votes needed to approve or reject a proposal.
+const DEFAULT_VOTE_THRESHOLD: u32 = 3;
+// Lowest threshold set_config accepts, more than one guardian has to agree.
+const MIN_VOTE_THRESHOLD: u32 = 2;
+// 30 days
+const DEFAULT_PROPOSAL_TTL: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+// 1 day, the shortest voting period set_config accepts
+const MIN_PROPOSAL_TTL: u64 = 24 * 60 * 60 * 1_000_000_000;
+// 1 day, the window emergency responders have to veto a passed proposal
+const DEFAULT_EXECUTION_DELAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+// 1 hour, the shortest veto window set_config accepts
+const MIN_EXECUTION_DELAY: u64 = 60 * 60 * 1_000_000_000;
+// 30 days, the longest veto window set_config accepts
+const MAX_EXECUTION_DELAY: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
 #[near]
 #[derive(BorshStorageKey)]
 enum EStorageKey {
     ApprovedProjects,
     ApprovedContracts,
     Proposals,
+    SortedApprovedProjects,
+    SortedApprovedContracts,
+    SortedProposals,
+    ArchivedProposals,
+    ProjectVersions,
+    ProjectVersionCounts,
+    ContractHistory,
+    ProposalsByProposer,
+    ProposalsByProject,
+    GuardianVotes,
+    GuardianStats,
+    ProjectRedirects,
+    RoleMembers,
+    SuspendedProjects,
+    VoteDelegations,
+    GuardianWeights,
+    StagedCode,
+    Denylist,
+    Reports,
+    ReportQueue,
+    MerkleNodes,
+    RemovedProjects,
+    OpenReportCounts,
+    ProposalTimeline,
+    ArchiveTimeline,
 }
 
 #[near(serializers=[borsh, json])]
+#[derive(Clone, PartialEq)]
 enum EProposalKind {
     NEW,
     UPDATE,
+    TRANSFER,
+    MERGE,
+    SPLIT,
+    RENAME,
+    // removes the project and unindexes its contracts
+    REMOVE,
+    // unpauses the scopes in pause_scopes, not tied to a project
+    UNPAUSE,
+    // deploys new code to the contract, not tied to a project
+    UPGRADE,
+    // add or remove the contracts in denylist, not tied to a project
+    DENYLIST,
+    UNDENYLIST,
 }
 
 #[near(serializers=[borsh, json])]
-enum EValueType {
-    STRING,
-    ARRAY,
+#[derive(PartialEq)]
+pub enum EProposalStatus {
+    PENDING,
+    // reached the vote threshold, waiting for execution
+    PASSED,
+    APPROVED,
+    REJECTED,
+    CANCELLED,
+    EXPIRED,
+    VETOED,
 }
 
 #[near(serializers=[borsh, json])]
-pub struct OldProjectInfo {
-    contract_ids: HashSet<AccountId>,
-    metadata: String,
-    pending_proposals: HashSet<String>
-}
-
-#[near(serializers=[borsh, json])]
-pub struct OldProposalInfo {
-    project_info: OldProjectInfo,
-    kind: EProposalKind,
-    // project_id will be null when kind is NEW
-    project_id: Option<String>,
-    votes: HashSet<AccountId>,
-    proposed_by: AccountId,
+enum EValueType {
+    STRING,
+    ARRAY,
 }
 
 #[near(serializers=[borsh, json])]
+#[derive(Clone)]
 pub struct ProjectInfo {
     contract_ids: HashSet<AccountId>,
     metadata: String,
@@ -55,6 +150,19 @@ pub struct ProjectMetadata {
     telegram_username: Option<String>,
     description: Option<String>,
     website_url: Option<String>,
+    // block timestamp in nanoseconds after which the audit report is outdated,
+    // the app submits empty strings for fields left blank
+    audit_expires_at: Option<String>,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct OldProposalInfo {
+    project_info: ProjectInfo,
+    kind: EProposalKind,
+    // project_id will be null when kind is NEW
+    project_id: Option<String>,
+    votes: HashSet<AccountId>,
+    proposed_by: AccountId,
 }
 
 #[near(serializers=[borsh, json])]
@@ -65,16 +173,204 @@ pub struct ProposalInfo {
     project_id: Option<String>,
     votes: HashSet<AccountId>,
     proposed_by: AccountId,
+    status: EProposalStatus,
+    rejections: HashSet<AccountId>,
+    created_at: U64,
+    // state of the second project after the change, set when kind is TRANSFER, MERGE or SPLIT
+    related_project: Option<ProjectInfo>,
+    // version of every affected project when the proposal was submitted, 0 if it did not exist
+    base_versions: BTreeMap<String, u32>,
+    // set once another proposal changed one of the affected projects, stale proposals can't be approved
+    stale: bool,
+    // previous versions of the proposal, oldest first
+    amendments: Vec<ProposalAmendment>,
+    // rationale attached to votes and plain comments, oldest first
+    comments: Vec<ProposalComment>,
+    // guardians whose vote or rejection was cast by a delegate, with the delegate
+    delegated_votes: BTreeMap<AccountId, AccountId>,
+    // weight of the current votes and rejections
+    tally: ProposalTally,
+    // set when the proposal passes
+    executable_at: Option<U64>,
+    // set by the first execute_proposal call
+    execution: Option<ExecutionProgress>,
+    // set when kind is UNPAUSE
+    pause_scopes: Option<BTreeSet<EPauseScope>>,
+    // set when kind is UPGRADE
+    upgrade: Option<CodeUpgrade>,
+    // set when kind is DENYLIST or UNDENYLIST
+    denylist: Option<DenylistChange>,
+}
+
+impl ProposalInfo {
+    fn new(kind: EProposalKind, project_id: Option<String>, project_info: ProjectInfo) -> Self {
+        Self {
+            project_info,
+            kind,
+            project_id,
+            votes: HashSet::new(),
+            proposed_by: env::predecessor_account_id(),
+            status: EProposalStatus::PENDING,
+            rejections: HashSet::new(),
+            created_at: U64(env::block_timestamp()),
+            related_project: None,
+            base_versions: BTreeMap::new(),
+            stale: false,
+            amendments: Vec::new(),
+            comments: Vec::new(),
+            delegated_votes: BTreeMap::new(),
+            tally: ProposalTally::default(),
+            executable_at: None,
+            execution: None,
+            pause_scopes: None,
+            upgrade: None,
+            denylist: None,
+        }
+    }
+
+    // Proposals that don't change projects leave their project info empty.
+    fn without_project(kind: EProposalKind) -> Self {
+        Self::new(
+            kind,
+            None,
+            ProjectInfo {
+                contract_ids: HashSet::new(),
+                metadata: String::new(),
+                pending_proposals: HashSet::new(),
+                project_id: String::new(),
+            },
+        )
+    }
+
+    fn is_project_proposal(&self) -> bool {
+        matches!(
+            self.kind,
+            EProposalKind::NEW
+                | EProposalKind::UPDATE
+                | EProposalKind::TRANSFER
+                | EProposalKind::MERGE
+                | EProposalKind::SPLIT
+                | EProposalKind::RENAME
+                | EProposalKind::REMOVE
+        )
+    }
+
+    fn target_project_id(&self) -> &str {
+        self.project_id
+            .as_deref()
+            .unwrap_or(&self.project_info.project_id)
+    }
+
+    // Every project id the proposal changes, the target project first.
+    fn affected_project_ids(&self) -> Vec<&str> {
+        if !self.is_project_proposal() {
+            return Vec::new();
+        }
+        let mut project_ids = vec![self.target_project_id()];
+        // the new id of a RENAME proposal
+        if self.project_info.project_id != self.target_project_id() {
+            project_ids.push(&self.project_info.project_id);
+        }
+        if let Some(related_project) = &self.related_project {
+            project_ids.push(&related_project.project_id);
+        }
+        project_ids
+    }
+
+    // Only pending proposals expire, passed proposals wait for their execution.
+    fn is_expired(&self, proposal_ttl: u64) -> bool {
+        self.status == EProposalStatus::PENDING
+            && env::block_timestamp() >= self.created_at.0.saturating_add(proposal_ttl)
+    }
+
+    // A passed proposal flagged stale can no longer be executed, it is closed like an expired one.
+    fn is_stale_after_passing(&self) -> bool {
+        self.status == EProposalStatus::PASSED && self.stale && self.execution.is_none()
+    }
+}
+
+#[near(serializers=[borsh, json])]
+pub struct ArchivedProposalInfo {
+    proposal: ProposalInfo,
+    // account whose call finalized the proposal
+    finalized_by: AccountId,
+    finalized_at: U64,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct Config {
+    // how long a proposal stays open for voting, in nanoseconds
+    proposal_ttl: U64,
+    // total guardian weight needed to approve or reject a proposal
+    vote_threshold: u32,
+    // time between passing and execution, in nanoseconds, during which a proposal can be vetoed
+    execution_delay: U64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            proposal_ttl: U64(DEFAULT_PROPOSAL_TTL),
+            vote_threshold: DEFAULT_VOTE_THRESHOLD,
+            execution_delay: U64(DEFAULT_EXECUTION_DELAY),
+        }
+    }
 }
 
 // Define the contract_id structure
 #[near(contract_state)]
 pub struct Contract {
     guardians: HashSet<AccountId>,
-    contract_project_index: IterableMap<AccountId, String>,
-    proposals: IterableMap<String, ProposalInfo>,
-    approved_projects: IterableMap<String, ProjectInfo>,
+    contract_project_index: TreeMap<AccountId, String>,
+    proposals: TreeMap<String, ProposalInfo>,
+    approved_projects: TreeMap<String, ProjectInfo>,
     running_id: u32,
+    archived_proposals: TreeMap<String, ArchivedProposalInfo>,
+    // keys of proposals and archived_proposals in creation order, used as sorted sets,
+    // see indexes::proposal_key
+    proposal_timeline: TreeMap<u128, ()>,
+    archive_timeline: TreeMap<u128, ()>,
+    config: Config,
+    project_versions: LookupMap<(String, u32), ProjectVersion>,
+    project_version_counts: LookupMap<String, u32>,
+    contract_history: LookupMap<AccountId, Vec<WhitelistPeriod>>,
+    proposals_by_proposer: LookupMap<AccountId, BTreeSet<u128>>,
+    // pending proposals by the project they target, NEW proposals use their proposed project id
+    proposals_by_project: LookupMap<String, BTreeSet<u128>>,
+    guardian_votes: LookupMap<(AccountId, u32), GuardianVote>,
+    guardian_stats: LookupMap<AccountId, GuardianStats>,
+    // ids of merged or renamed projects, pointing to their current id
+    project_redirects: LookupMap<String, String>,
+    // members of every role but GUARDIAN, guardians are kept in guardians
+    role_members: LookupMap<ERole, HashSet<AccountId>>,
+    suspended_projects: TreeMap<String, ProjectSuspension>,
+    // by delegating guardian
+    vote_delegations: LookupMap<AccountId, VoteDelegation>,
+    // guardians without an entry weigh DEFAULT_GUARDIAN_WEIGHT
+    guardian_weights: LookupMap<AccountId, u32>,
+    // proposal whose execution was started and is not finished yet
+    executing_proposal: Option<String>,
+    pause_status: PauseStatus,
+    // wasm staged for UPGRADE proposals, by code hash
+    staged_code: LookupMap<Base58CryptoHash, CodeStaging>,
+    // bytes staged across every code hash, at most MAX_STAGED_CODE_SIZE
+    staged_code_size: u32,
+    // manages roles, null once renounced
+    owner: Option<AccountId>,
+    // set between the two steps of an ownership transfer
+    pending_owner: Option<AccountId>,
+    // contracts known to be malicious
+    denylist: TreeMap<AccountId, DenylistEntry>,
+    reports: TreeMap<u32, CommunityReport>,
+    // ids of the open reports, used as a sorted set
+    report_queue: TreeMap<u32, ()>,
+    report_count: u32,
+    // open reports by the current id of the reported project, moved along on renames and merges
+    open_report_counts: LookupMap<String, u32>,
+    // sparse Merkle tree over contract_project_index, by depth and key prefix
+    merkle_nodes: LookupMap<(u16, CryptoHash), MerkleNode>,
+    // ids of removed projects, reserved so the aliases pointing to them never reach another project
+    removed_project_ids: LookupSet<String>,
 }
 
 #[near(serializers=[borsh])]
@@ -83,7 +379,7 @@ pub struct OldContract {
     guardians: HashSet<AccountId>,
     contract_project_index: IterableMap<AccountId, String>,
     proposals: IterableMap<String, OldProposalInfo>,
-    approved_projects: IterableMap<String, OldProjectInfo>,
+    approved_projects: IterableMap<String, ProjectInfo>,
     running_id: u32,
 }
 
@@ -99,10 +395,40 @@ impl Default for Contract {
     fn default() -> Self {
         Self {
             guardians: HashSet::new(),
-            contract_project_index: IterableMap::new(EStorageKey::ApprovedContracts),
-            proposals: IterableMap::new(EStorageKey::Proposals),
-            approved_projects: IterableMap::new(EStorageKey::ApprovedProjects),
+            contract_project_index: TreeMap::new(EStorageKey::SortedApprovedContracts),
+            proposals: TreeMap::new(EStorageKey::SortedProposals),
+            approved_projects: TreeMap::new(EStorageKey::SortedApprovedProjects),
             running_id: 0,
+            archived_proposals: TreeMap::new(EStorageKey::ArchivedProposals),
+            proposal_timeline: TreeMap::new(EStorageKey::ProposalTimeline),
+            archive_timeline: TreeMap::new(EStorageKey::ArchiveTimeline),
+            config: Config::default(),
+            project_versions: LookupMap::new(EStorageKey::ProjectVersions),
+            project_version_counts: LookupMap::new(EStorageKey::ProjectVersionCounts),
+            contract_history: LookupMap::new(EStorageKey::ContractHistory),
+            proposals_by_proposer: LookupMap::new(EStorageKey::ProposalsByProposer),
+            proposals_by_project: LookupMap::new(EStorageKey::ProposalsByProject),
+            guardian_votes: LookupMap::new(EStorageKey::GuardianVotes),
+            guardian_stats: LookupMap::new(EStorageKey::GuardianStats),
+            project_redirects: LookupMap::new(EStorageKey::ProjectRedirects),
+            role_members: LookupMap::new(EStorageKey::RoleMembers),
+            suspended_projects: TreeMap::new(EStorageKey::SuspendedProjects),
+            vote_delegations: LookupMap::new(EStorageKey::VoteDelegations),
+            guardian_weights: LookupMap::new(EStorageKey::GuardianWeights),
+            executing_proposal: None,
+            pause_status: PauseStatus::default(),
+            staged_code: LookupMap::new(EStorageKey::StagedCode),
+            staged_code_size: 0,
+            // the contract account owns the contract until it transfers the ownership
+            owner: Some(env::current_account_id()),
+            pending_owner: None,
+            denylist: TreeMap::new(EStorageKey::Denylist),
+            reports: TreeMap::new(EStorageKey::Reports),
+            report_queue: TreeMap::new(EStorageKey::ReportQueue),
+            report_count: 0,
+            open_report_counts: LookupMap::new(EStorageKey::OpenReportCounts),
+            merkle_nodes: LookupMap::new(EStorageKey::MerkleNodes),
+            removed_project_ids: LookupSet::new(EStorageKey::RemovedProjects),
         }
     }
 }
@@ -114,32 +440,91 @@ impl Contract {
     #[init(ignore_state)]
     pub fn migrate() -> Self {
         let mut old_state: OldContract = env::state_read().expect("failed");
-        old_state.contract_project_index.clear();
-        old_state.approved_projects.clear();
-        old_state.proposals.clear();
 
-        Self {
-            contract_project_index: IterableMap::new(EStorageKey::ApprovedContracts),
-            proposals: IterableMap::new(EStorageKey::Proposals),
-            approved_projects: IterableMap::new(EStorageKey::ApprovedProjects),
+        // IterableMap has no stable order, so every entry is moved into the sorted maps
+        let mut state = Self {
+            guardians: old_state.guardians,
             running_id: old_state.running_id,
-            guardians: old_state.guardians
+            ..Self::default()
+        };
+
+        // history starts at the upgrade, the existing state becomes the first recorded version
+        for (contract_id, project_id) in old_state.contract_project_index.drain() {
+            state.index_contract(&contract_id, &project_id);
+        }
+        for (project_id, project) in old_state.approved_projects.drain() {
+            state.approved_projects.insert(project_id.clone(), project);
+            state.record_project_version(&project_id, None);
+        }
+        for (proposal_id, old_proposal) in old_state.proposals.drain() {
+            // creation time was not recorded before, so the voting period starts now
+            let mut proposal = ProposalInfo::new(
+                old_proposal.kind,
+                old_proposal.project_id,
+                old_proposal.project_info,
+            );
+            proposal.votes = old_proposal.votes;
+            proposal.proposed_by = old_proposal.proposed_by;
+            state.insert_proposal(proposal_id, proposal);
         }
+
+        state
     }
 
-    #[private]
     pub fn add_guardian(&mut self, account_id: AccountId) {
-        self.guardians.insert(account_id);
+        self.grant_role(account_id, ERole::GUARDIAN);
     }
 
-    #[private]
     pub fn revoke_guardian(&mut self, account_id: AccountId) {
-        let removed = self.guardians.remove(&account_id);
-        if removed == true {
-            for (_, info) in self.proposals.iter_mut() {
-                info.votes.remove(&account_id);
-            }
+        self.revoke_role(account_id, ERole::GUARDIAN);
+    }
+
+    // The threshold must stay reachable by the current guardians, and can't drop below
+    // MIN_VOTE_THRESHOLD or the weight of the heaviest guardian, so neither a single admin nor
+    // a single guardian can bypass the guardian quorum. Likewise the execution delay keeps a
+    // veto window of at least MIN_EXECUTION_DELAY, without holding proposals back for more
+    // than MAX_EXECUTION_DELAY.
+    pub fn set_config(&mut self, config: Config) {
+        self.assert_role(ERole::ADMIN);
+        let total_guardian_weight = self.total_guardian_weight();
+        if config.vote_threshold < MIN_VOTE_THRESHOLD || config.vote_threshold > total_guardian_weight {
+            panic!(
+                "Vote threshold must be between {} and the total guardian weight {}",
+                MIN_VOTE_THRESHOLD, total_guardian_weight
+            );
+        }
+        let heaviest_guardian_weight = self.heaviest_guardian_weight();
+        if config.vote_threshold <= heaviest_guardian_weight {
+            panic!(
+                "Vote threshold must exceed the heaviest guardian weight {}",
+                heaviest_guardian_weight
+            );
         }
+        // a shorter voting period would expire proposals before guardians get to vote
+        if config.proposal_ttl.0 < MIN_PROPOSAL_TTL {
+            panic!("Proposal TTL must be at least {} nanoseconds", MIN_PROPOSAL_TTL);
+        }
+        if config.execution_delay.0 < MIN_EXECUTION_DELAY || config.execution_delay.0 > MAX_EXECUTION_DELAY {
+            panic!(
+                "Execution delay must be between {} and {} nanoseconds",
+                MIN_EXECUTION_DELAY, MAX_EXECUTION_DELAY
+            );
+        }
+        self.config = config;
+    }
+
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
+
+    fn next_proposal_id(&mut self) -> String {
+        let (new_running_id, proposal_id) = Contract::generate_id(self.running_id);
+        self.running_id = new_running_id;
+
+        if self.proposals.contains_key(&proposal_id) {
+            panic!("proposal id collision, please try again later");
+        }
+        proposal_id
     }
 
     fn generate_id(last_running_id: u32) -> (u32, String) {
@@ -168,18 +553,16 @@ impl Contract {
         metadata: String,
         project_id: String,
     ) -> Option<String> {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
         if env::attached_deposit() != NearToken::from_near(1) {
             panic!("Creating proposal requires depositing 1 NEAR.")
         }
 
-        if project_id.len() == 0 {
+        if project_id.is_empty() {
             panic!("Project id length must be greater than 0")
         }
 
-        let existing_project_option =
-            self.approved_projects.get_mut(&project_id.clone());
-
-        if existing_project_option.is_some() {
+        if self.is_project_id_taken(&project_id) {
             panic!("Project id is occupied, please use another project id.")
         }
 
@@ -188,18 +571,16 @@ impl Contract {
         let (new_running_id, proposal_id) = Contract::generate_id(self.running_id);
         self.running_id = new_running_id;
 
-        let proposal = ProposalInfo {
-            project_info: ProjectInfo {
+        let proposal = ProposalInfo::new(
+            EProposalKind::NEW,
+            None,
+            ProjectInfo {
                 contract_ids: contract_ids.clone(),
                 metadata,
                 pending_proposals: HashSet::new(),
                 project_id
             },
-            proposed_by: env::predecessor_account_id(),
-            kind: EProposalKind::NEW,
-            project_id: None,
-            votes: HashSet::new(),
-        };
+        );
 
         if self.proposals.contains_key(&proposal_id) {
             panic!("id collision, please try again later");
@@ -210,11 +591,23 @@ impl Contract {
                     panic!("{} is associated with project {} already.", contract_id.clone(), associated_project_id.clone())
                 }
             }
-            self.proposals.insert(proposal_id.clone(), proposal);
+            self.insert_proposal(proposal_id.clone(), proposal);
             Option::from(proposal_id)
         }
     }
 
+    // Curators can fix the metadata of a project without a proposal.
+    pub fn curate_project_metadata(&mut self, project_id: String, metadata: String) -> bool {
+        self.assert_role(ERole::CURATOR);
+        serde_json::from_str::<ProjectMetadata>(&metadata).expect("Incorrect metadata structure");
+
+        let project = self.approved_projects.get_mut(&project_id).expect("Project not found");
+        project.metadata = metadata;
+        self.record_project_version(&project_id, None);
+        self.flag_stale_project_proposals(vec![project_id]);
+        true
+    }
+
     #[payable]
     pub fn update_project(
         &mut self,
@@ -222,6 +615,7 @@ impl Contract {
         contract_ids: HashSet<AccountId>,
         metadata: String
     ) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
         if env::attached_deposit() != NearToken::from_near(1) {
             panic!("Creating proposal requires depositing 1 NEAR.")
         }
@@ -232,28 +626,22 @@ impl Contract {
             let (new_running_id, proposal_id) = Contract::generate_id(self.running_id);
             self.running_id = new_running_id;
 
-            let proposal = ProposalInfo {
-                project_info: ProjectInfo {
+            let proposal = ProposalInfo::new(
+                EProposalKind::UPDATE,
+                Option::from(project_id.clone()),
+                ProjectInfo {
                     contract_ids,
                     metadata,
                     pending_proposals: HashSet::new(),
                     project_id: project_id.clone(),
                 },
-                proposed_by: env::predecessor_account_id(),
-                project_id: Option::from(project_id.clone()),
-                kind: EProposalKind::UPDATE,
-                votes: HashSet::new(),
-            };
+            );
+            self.assert_no_conflicts(&proposal);
 
             if self.proposals.contains_key(&proposal_id) {
                 panic!("proposal id collision, please try again later");
             } else {
-                self.proposals.insert(proposal_id.clone(), proposal);
-                let project_option = self.approved_projects.get_mut(&project_id.clone());
-                if project_option.is_some() {
-                    let project = project_option.unwrap();
-                    project.pending_proposals.insert(proposal_id.clone());
-                }
+                self.insert_proposal(proposal_id.clone(), proposal);
                 proposal_id
             }
         } else {
@@ -261,179 +649,268 @@ impl Contract {
         }
     }
 
-    pub fn vote_proposal(&mut self, proposal_id: String) -> bool {
-        let is_one_of_guardians = self.guardians.contains(&env::predecessor_account_id());
-        if is_one_of_guardians == true {
-            let proposal_option = self.proposals.get_mut(&proposal_id);
-            match proposal_option {
-                Some(proposal) => {
-                    proposal.votes.insert(env::predecessor_account_id());
-
-                    if proposal.votes.len() >= 3 {
-                        log!("Vote count reached 3, attempting to perform the proposal changes");
-
-                        Promise::new(proposal.proposed_by.clone())
-                            .transfer(NearToken::from_near(1));
-                        log!("Refunded 1 NEAR to {}", proposal.proposed_by);
-
-                        let mut project_info = ProjectInfo {
-                            contract_ids: proposal.project_info.contract_ids.clone(),
-                            metadata: proposal.project_info.metadata.clone(),
-                            pending_proposals: HashSet::new(),
-                            project_id: proposal.project_info.project_id.clone(),
-                        };
-
-                        match proposal.kind {
-                            EProposalKind::NEW => {
-                                let existing_project_option =
-                                    self.approved_projects.get_mut(&project_info.project_id.clone());
-                                match existing_project_option {
-                                    None => {
-                                        for contract_id in proposal.project_info.contract_ids.iter() {
-                                            let contract_id_option =
-                                                self.contract_project_index.get(&contract_id.clone());
-                                            match contract_id_option {
-                                                None => {
-                                                    self.contract_project_index.insert(contract_id.clone(), project_info.project_id.clone());
-                                                }
-                                                Some(associated_project_id) => {
-                                                    log!("Skipping {}, as it is paired with {}", contract_id, associated_project_id);
-                                                }
-                                            }
-                                        }
-
-                                        self.approved_projects.insert(project_info.project_id.clone(), project_info);
-                                    }
-                                    Some(_) => {
-                                        log!("Skipping add project as the project id is occupied.");
-                                    }
-                                }
-                            }
-                            EProposalKind::UPDATE => {
-                                let project_id_option = proposal.project_id.clone();
-                                match project_id_option {
-                                    None => {
-                                        log!("Project ID is missing in the proposal, and no update will be performed")
-                                    }
-                                    Some(project_id) => {
-                                        let existing_project_option =
-                                            self.approved_projects.get_mut(&project_id);
-                                        match existing_project_option {
-                                            None => {
-                                                log!("Project not found for the proposal, and no update will be performed")
-                                            }
-                                            Some(existing_project) => {
-                                                for contract_id in
-                                                    existing_project.contract_ids.iter()
-                                                {
-                                                    self.contract_project_index.remove(contract_id);
-                                                }
-
-                                                for contract_id in
-                                                    proposal.project_info.contract_ids.iter()
-                                                {
-                                                    let contract_id_count_option = self
-                                                        .contract_project_index
-                                                        .get(&contract_id.clone());
-                                                    match contract_id_count_option {
-                                                        None => {
-                                                            self.contract_project_index.insert(contract_id.clone(), project_id.clone());
-                                                        }
-                                                        Some(associated_project_id) => {
-                                                            log!("Skipping {}, as it is paired with {}", contract_id, associated_project_id);
-                                                        }
-                                                    }
-                                                }
-                                                
-
-                                                project_info.pending_proposals = existing_project.pending_proposals.clone();
-                                                project_info.pending_proposals.remove(&proposal_id);
-                                                self.approved_projects
-                                                    .insert(project_id, project_info);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        self.proposals.remove(&proposal_id);
-                    }
-                    true
+    pub fn vote_proposal(&mut self, proposal_id: String, comment: Option<String>) -> bool {
+        let proposal_ttl = self.config.proposal_ttl.0;
+        let proposal_option = self.proposals.get(&proposal_id);
+        match proposal_option {
+            Some(proposal) => {
+                self.assert_proposal_not_paused(proposal, EPauseScope::VOTING);
+                let voters = self.voting_guardians(proposal);
+                let proposal = self.proposals.get_mut(&proposal_id).unwrap();
+                if proposal.is_expired(proposal_ttl) {
+                    panic!("Proposal has expired.");
                 }
-                None => {
-                    panic!("Proposal not found");
+                proposal.assert_open_for_voting();
+                if proposal.stale {
+                    panic!("Proposal is stale, the project changed since it was submitted.");
                 }
+
+                for voter in voters.iter() {
+                    proposal.rejections.remove(voter);
+                    proposal.votes.insert(voter.clone());
+                    proposal.attribute_vote(voter);
+                }
+                proposal.add_comment(Some(EVoteAction::APPROVE), comment);
+                proposal.tally = weights::tally(&self.guardian_weights, proposal);
+
+                if proposal.tally.approval_weight() >= self.config.vote_threshold {
+                    log!("Approval weight reached {}, the proposal passed", self.config.vote_threshold);
+                    self.pass_proposal(&proposal_id);
+                }
+                self.record_guardian_vote(&voters, &proposal_id, EVoteAction::APPROVE);
+                true
+            }
+            None => {
+                panic!("Proposal not found");
+            }
+        }
+    }
+
+    pub fn reject_proposal(&mut self, proposal_id: String, comment: Option<String>) -> bool {
+        let proposal_ttl = self.config.proposal_ttl.0;
+        let proposal_option = self.proposals.get(&proposal_id);
+        match proposal_option {
+            Some(proposal) => {
+                self.assert_proposal_not_paused(proposal, EPauseScope::VOTING);
+                let voters = self.voting_guardians(proposal);
+                let proposal = self.proposals.get_mut(&proposal_id).unwrap();
+                if proposal.is_expired(proposal_ttl) {
+                    panic!("Proposal has expired.");
+                }
+                proposal.assert_open_for_voting();
+
+                for voter in voters.iter() {
+                    proposal.votes.remove(voter);
+                    proposal.rejections.insert(voter.clone());
+                    proposal.attribute_vote(voter);
+                }
+                proposal.add_comment(Some(EVoteAction::REJECT), comment);
+                proposal.tally = weights::tally(&self.guardian_weights, proposal);
+
+                if proposal.tally.rejection_weight() >= self.config.vote_threshold {
+                    // the deposit is kept, rejected proposals are not refunded
+                    log!("Rejection weight reached {}, the proposal is rejected", self.config.vote_threshold);
+                    self.archive_proposal(&proposal_id, EProposalStatus::REJECTED);
+                }
+                self.record_guardian_vote(&voters, &proposal_id, EVoteAction::REJECT);
+                true
+            }
+            None => {
+                panic!("Proposal not found");
             }
-        } else {
-            panic!("You are not a qualified guardian");
         }
     }
 
+    // Withdraws the caller's vote, and the votes they cast as a delegate.
     pub fn withdraw_vote_on_proposal(&mut self, proposal_id: String) -> bool {
-        let is_one_of_guardians = self.guardians.contains(&env::predecessor_account_id());
-        if is_one_of_guardians == true {
-            let proposal_option = self.proposals.get_mut(&proposal_id);
-            match proposal_option {
-                Some(proposal) => {
-                    let is_vote_removed = proposal.votes.remove(&env::predecessor_account_id());
-                    if is_vote_removed == true {
-                        true
-                    } else {
-                        panic!("You did not vote the proposal before.");
-                    }
+        if let Some(proposal) = self.proposals.get(&proposal_id) {
+            self.assert_proposal_not_paused(proposal, EPauseScope::VOTING);
+        }
+        let proposal_option = self.proposals.get_mut(&proposal_id);
+        match proposal_option {
+            Some(proposal) => {
+                proposal.assert_open_for_voting();
+                let caller = env::predecessor_account_id();
+                let mut voters: Vec<AccountId> = proposal
+                    .delegated_votes
+                    .iter()
+                    .filter(|(_, delegate)| *delegate == &caller)
+                    .map(|(guardian, _)| guardian.clone())
+                    .collect();
+                if self.guardians.contains(&caller) {
+                    voters.push(caller);
+                } else if voters.is_empty() {
+                    panic!("You are not a qualified guardian");
                 }
-                None => {
-                    panic!("Unable to find the proposal.");
+
+                voters.retain(|voter| proposal.votes.remove(voter) || proposal.rejections.remove(voter));
+                for voter in voters.iter() {
+                    proposal.delegated_votes.remove(voter);
+                }
+                proposal.tally = weights::tally(&self.guardian_weights, proposal);
+
+                if !voters.is_empty() {
+                    self.record_guardian_vote(&voters, &proposal_id, EVoteAction::WITHDRAW);
+                    true
+                } else {
+                    panic!("You did not vote the proposal before.");
                 }
             }
-        } else {
-            panic!("You are not a qualified guardian");
+            None => {
+                panic!("Unable to find the proposal.");
+            }
+        }
+    }
+
+    pub fn cancel_proposal(&mut self, proposal_id: String) -> bool {
+        match self.proposals.get(&proposal_id) {
+            Some(proposal) => {
+                if proposal.proposed_by != env::predecessor_account_id() {
+                    panic!("Only the proposer can cancel the proposal.");
+                }
+                // passed proposals are left to their execution, or to a veto
+                if proposal.status != EProposalStatus::PENDING {
+                    panic!("Only pending proposals can be cancelled.");
+                }
+
+                Contract::refund_deposit(proposal);
+                self.archive_proposal(&proposal_id, EProposalStatus::CANCELLED);
+                true
+            }
+            None => {
+                panic!("Proposal not found");
+            }
+        }
+    }
+
+    // Anyone can close a proposal once its voting period is over, or once it passed and went stale.
+    // The deposit goes back to the proposer.
+    pub fn expire_proposal(&mut self, proposal_id: String) -> bool {
+        match self.proposals.get(&proposal_id) {
+            Some(proposal) => {
+                if !proposal.is_expired(self.config.proposal_ttl.0) && !proposal.is_stale_after_passing() {
+                    panic!("Proposal has not expired yet.");
+                }
+
+                Contract::refund_deposit(proposal);
+                self.archive_proposal(&proposal_id, EProposalStatus::EXPIRED);
+                true
+            }
+            None => {
+                panic!("Proposal not found");
+            }
+        }
+    }
+
+    fn assert_proposal_deposit() {
+        if env::attached_deposit() != NearToken::from_near(1) {
+            panic!("Creating proposal requires depositing 1 NEAR.")
         }
     }
 
+    fn refund_deposit(proposal: &ProposalInfo) {
+        Promise::new(proposal.proposed_by.clone()).transfer(NearToken::from_near(1));
+        log!("Refunded 1 NEAR to {}", proposal.proposed_by);
+    }
+
+    // Moves a finalized proposal out of the pending proposals, keeping its votes for auditing.
+    fn archive_proposal(&mut self, proposal_id: &str, status: EProposalStatus) {
+        let mut proposal = self.proposals.remove(proposal_id).expect("Proposal not found");
+        proposal.status = status;
+        self.unindex_proposal(proposal_id, &proposal);
+        self.record_guardian_participation(&proposal);
+        self.release_staged_code(&proposal);
+
+        self.archive_timeline.insert(indexes::proposal_key(proposal_id), ());
+        self.archived_proposals.insert(
+            proposal_id.to_string(),
+            ArchivedProposalInfo {
+                proposal,
+                finalized_by: env::predecessor_account_id(),
+                finalized_at: U64(env::block_timestamp()),
+            },
+        );
+    }
+
+    // Denylisted contracts are never reported as whitelisted.
     pub fn check_contract_whitelisted(self, contract_id: AccountId) -> bool {
-        self.contract_project_index.contains_key(&contract_id)
+        self.contract_verdict(&contract_id) == EVerdict::TRUSTED
     }
 
-    pub fn list_projects(&self, from_index: i32, limit: i32) -> Vec<(&String, &ProjectInfo)> {
-        self.approved_projects
-            .iter()
-            .rev()
-            .skip(from_index as usize)
-            .take(limit as usize)
-            .collect()
+    pub fn list_projects(
+        &self,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProjectInfo)> {
+        Page::collect(
+            self.approved_projects.range(pagination::after(cursor)),
+            limit,
+            self.approved_projects.len(),
+            |(project_id, _)| (*project_id).clone(),
+        )
     }
 
-    pub fn list_proposals(&self, from_index: i32, limit: i32) -> Vec<(&String, &ProposalInfo)> {
-        self.proposals
-            .iter()
-            .rev()
-            .skip(from_index as usize)
-            .take(limit as usize)
-            .collect()
+    // Newest first.
+    pub fn list_proposals(
+        &self,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProposalInfo)> {
+        Page::collect(
+            self.newest_proposals(cursor),
+            limit,
+            self.proposals.len(),
+            |(proposal_id, _)| (*proposal_id).clone(),
+        )
     }
 
-    pub fn list_contracts(&self, from_index: i32, limit: i32) -> Vec<(&AccountId, &String)> {
-        self.contract_project_index
-            .iter()
-            .rev()
-            .skip(from_index as usize)
-            .take(limit as usize)
-            .collect()
+    pub fn list_contracts(
+        &self,
+        cursor: Option<AccountId>,
+        limit: Option<u32>,
+    ) -> Page<AccountId, (&AccountId, &String)> {
+        Page::collect(
+            self.contract_project_index.range(pagination::after(cursor)),
+            limit,
+            self.contract_project_index.len(),
+            |(contract_id, _)| (*contract_id).clone(),
+        )
     }
 
     pub fn get_project_id_by_contract_id(&self, contract_id: AccountId) -> Option<&String> {
         self.contract_project_index.get(&contract_id)
     }
 
+    // Ids of merged or renamed projects resolve to their current project.
     pub fn get_project_by_id(&self, project_id: String) -> Option<&ProjectInfo> {
-        self.approved_projects.get(&project_id)
+        self.approved_projects.get(self.current_project_id(&project_id))
     }
 
     pub fn get_proposal_by_id(&self, proposal_id: String) -> Option<&ProposalInfo> {
         self.proposals.get(&proposal_id)
     }
 
+    // Newest first, by creation of the proposal.
+    pub fn list_archived_proposals(
+        &self,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ArchivedProposalInfo)> {
+        Page::collect(
+            self.archive_timeline
+                .range(pagination::before(cursor.as_deref().map(indexes::proposal_key)))
+                .rev()
+                .filter_map(|(key, _)| self.archived_proposals.get_key_value(&key.to_string())),
+            limit,
+            self.archived_proposals.len(),
+            |(proposal_id, _)| (*proposal_id).clone(),
+        )
+    }
+
+    pub fn get_archived_proposal_by_id(&self, proposal_id: String) -> Option<&ArchivedProposalInfo> {
+        self.archived_proposals.get(&proposal_id)
+    }
+
     pub fn get_metadata_structure() -> Vec<MetadataStructure>{
         Vec::from([
             MetadataStructure {
@@ -466,6 +943,12 @@ impl Contract {
                 is_required: false,
                 label: "Audit Report URL".to_string()
             },
+            MetadataStructure {
+                key: "audit_expires_at".to_string(),
+                value_type: EValueType::STRING,
+                is_required: false,
+                label: "Audit Expiry (timestamp in nanoseconds)".to_string()
+            },
         ])
     }
 }
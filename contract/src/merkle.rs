@@ -0,0 +1,217 @@
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::{env, near, AccountId, CryptoHash};
+
+// Sparse Merkle tree over contract_project_index. A leaf sits at the shallowest depth where
+// no other key shares its path, so the root only depends on the entries, and an update
+// touches about log2(entries) nodes.
+//   key        = sha256(contract_id), read from its most significant bit
+//   leaf hash  = sha256(0x00 || key || sha256(project_id))
+//   inner hash = sha256(0x01 || left || right)
+//   empty hash = 32 zero bytes
+const EMPTY_HASH: CryptoHash = [0; 32];
+
+#[near(serializers=[borsh])]
+pub enum MerkleNode {
+    LEAF { key: CryptoHash, value_hash: CryptoHash },
+    INNER { hash: CryptoHash },
+}
+
+impl MerkleNode {
+    fn hash(&self) -> CryptoHash {
+        match self {
+            MerkleNode::LEAF { key, value_hash } => leaf_hash(key, value_hash),
+            MerkleNode::INNER { hash } => *hash,
+        }
+    }
+}
+
+#[near(serializers=[json])]
+pub struct ProofLeaf {
+    key: Base58CryptoHash,
+    value_hash: Base58CryptoHash,
+}
+
+#[near(serializers=[json])]
+pub struct WhitelistProof {
+    root: Base58CryptoHash,
+    key: Base58CryptoHash,
+    // null for a non-inclusion proof
+    project_id: Option<String>,
+    // from the root down
+    siblings: Vec<Base58CryptoHash>,
+    // set for a non-inclusion proof whose path ends on the leaf of another contract
+    other_leaf: Option<ProofLeaf>,
+}
+
+fn leaf_hash(key: &CryptoHash, value_hash: &CryptoHash) -> CryptoHash {
+    let mut bytes = Vec::with_capacity(65);
+    bytes.push(0);
+    bytes.extend_from_slice(key);
+    bytes.extend_from_slice(value_hash);
+    env::sha256_array(&bytes)
+}
+
+fn inner_hash(left: &CryptoHash, right: &CryptoHash) -> CryptoHash {
+    let mut bytes = Vec::with_capacity(65);
+    bytes.push(1);
+    bytes.extend_from_slice(left);
+    bytes.extend_from_slice(right);
+    env::sha256_array(&bytes)
+}
+
+fn bit(key: &CryptoHash, depth: u16) -> bool {
+    (key[depth as usize / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+// The first `depth` bits of the key, the rest zeroed. Nodes are stored under (depth, prefix).
+fn prefix(key: &CryptoHash, depth: u16) -> CryptoHash {
+    let mut prefix = [0; 32];
+    for (index, byte) in key.iter().enumerate() {
+        let bits = (depth as usize).saturating_sub(index * 8).min(8);
+        if bits > 0 {
+            prefix[index] = byte & (0xff << (8 - bits));
+        }
+    }
+    prefix
+}
+
+// Prefix of the sibling of the node at `depth` on the key's path.
+fn sibling_prefix(key: &CryptoHash, depth: u16) -> CryptoHash {
+    let mut prefix = prefix(key, depth);
+    let flipped = depth - 1;
+    prefix[flipped as usize / 8] ^= 1 << (7 - flipped % 8);
+    prefix
+}
+
+impl Contract {
+    fn merkle_node(&self, depth: u16, key: &CryptoHash) -> Option<&MerkleNode> {
+        self.merkle_nodes.get(&(depth, prefix(key, depth)))
+    }
+
+    fn merkle_hash(&self, depth: u16, prefix: CryptoHash) -> CryptoHash {
+        self.merkle_nodes
+            .get(&(depth, prefix))
+            .map_or(EMPTY_HASH, MerkleNode::hash)
+    }
+
+    // Recomputes the inner nodes on the key's path above `depth`.
+    fn rehash_merkle_path(&mut self, key: &CryptoHash, depth: u16) {
+        for depth in (0..depth).rev() {
+            let mut left = prefix(key, depth + 1);
+            let index = depth as usize / 8;
+            left[index] &= !(1 << (7 - depth % 8));
+            let mut right = left;
+            right[index] |= 1 << (7 - depth % 8);
+
+            let hash = inner_hash(&self.merkle_hash(depth + 1, left), &self.merkle_hash(depth + 1, right));
+            self.merkle_nodes
+                .insert((depth, prefix(key, depth)), MerkleNode::INNER { hash });
+        }
+    }
+
+    pub(crate) fn merkle_insert(&mut self, contract_id: &AccountId, project_id: &str) {
+        let key = env::sha256_array(contract_id.as_bytes());
+        let value_hash = env::sha256_array(project_id.as_bytes());
+
+        let mut depth = 0;
+        while let Some(MerkleNode::INNER { .. }) = self.merkle_node(depth, &key) {
+            depth += 1;
+        }
+
+        let mut leaf_depth = depth;
+        if let Some(MerkleNode::LEAF { key: other_key, value_hash: other_value_hash }) = self.merkle_node(depth, &key) {
+            let (other_key, other_value_hash) = (*other_key, *other_value_hash);
+            if other_key != key {
+                // both leaves go below the first bit where the keys differ
+                while bit(&key, leaf_depth) == bit(&other_key, leaf_depth) {
+                    leaf_depth += 1;
+                }
+                leaf_depth += 1;
+                self.merkle_nodes.insert(
+                    (leaf_depth, prefix(&other_key, leaf_depth)),
+                    MerkleNode::LEAF { key: other_key, value_hash: other_value_hash },
+                );
+            }
+        }
+
+        self.merkle_nodes
+            .insert((leaf_depth, prefix(&key, leaf_depth)), MerkleNode::LEAF { key, value_hash });
+        self.rehash_merkle_path(&key, leaf_depth);
+    }
+
+    pub(crate) fn merkle_remove(&mut self, contract_id: &AccountId) {
+        let key = env::sha256_array(contract_id.as_bytes());
+
+        let mut depth = 0;
+        loop {
+            match self.merkle_node(depth, &key) {
+                Some(MerkleNode::INNER { .. }) => depth += 1,
+                Some(MerkleNode::LEAF { key: leaf_key, .. }) if *leaf_key == key => break,
+                _ => return,
+            }
+        }
+        self.merkle_nodes.remove(&(depth, prefix(&key, depth)));
+
+        // a leaf left alone in its subtree moves up to the root of the subtree
+        if depth > 0 {
+            let sibling = (depth, sibling_prefix(&key, depth));
+            if let Some(MerkleNode::LEAF { .. }) = self.merkle_nodes.get(&sibling) {
+                let leaf = self.merkle_nodes.remove(&sibling).unwrap();
+                depth -= 1;
+                while depth > 0 && !self.merkle_nodes.contains_key(&(depth, sibling_prefix(&key, depth))) {
+                    self.merkle_nodes.remove(&(depth, prefix(&key, depth)));
+                    depth -= 1;
+                }
+                self.merkle_nodes.insert((depth, prefix(&key, depth)), leaf);
+            }
+        }
+        self.rehash_merkle_path(&key, depth);
+    }
+
+    fn merkle_root(&self) -> CryptoHash {
+        self.merkle_hash(0, EMPTY_HASH)
+    }
+}
+
+#[near]
+impl Contract {
+    pub fn get_whitelist_root(&self) -> Base58CryptoHash {
+        self.merkle_root().into()
+    }
+
+    // Proves that a contract is whitelisted under its project, or that it is not whitelisted.
+    // Hash the terminal node (the contract's leaf, the other leaf or the empty hash) with the
+    // siblings from the last one up, on the side given by the key bit at that depth.
+    pub fn get_whitelist_proof(&self, contract_id: AccountId) -> WhitelistProof {
+        let key = env::sha256_array(contract_id.as_bytes());
+        let mut siblings = Vec::new();
+        let mut other_leaf = None;
+
+        let mut depth = 0;
+        loop {
+            match self.merkle_node(depth, &key) {
+                Some(MerkleNode::INNER { .. }) => {
+                    siblings.push(self.merkle_hash(depth + 1, sibling_prefix(&key, depth + 1)).into());
+                    depth += 1;
+                }
+                Some(MerkleNode::LEAF { key: leaf_key, value_hash }) if *leaf_key != key => {
+                    other_leaf = Some(ProofLeaf {
+                        key: (*leaf_key).into(),
+                        value_hash: (*value_hash).into(),
+                    });
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        WhitelistProof {
+            root: self.get_whitelist_root(),
+            key: key.into(),
+            project_id: self.contract_project_index.get(&contract_id).cloned(),
+            siblings,
+            other_leaf,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{Contract, ContractExt};
+use near_sdk::{env, log, near, AccountId};
+
+impl Contract {
+    pub(crate) fn is_owner(&self, account_id: &AccountId) -> bool {
+        self.owner.as_ref() == Some(account_id)
+    }
+
+    pub(crate) fn assert_owner(&self) {
+        if !self.is_owner(&env::predecessor_account_id()) {
+            panic!("Only the owner can call this method.");
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // First step of an ownership transfer, the new owner has to accept it.
+    // Proposing again replaces the pending owner.
+    pub fn propose_owner(&mut self, account_id: AccountId) -> bool {
+        self.assert_owner();
+        log!("{} is proposed as the new owner", account_id);
+        self.pending_owner = Some(account_id);
+        true
+    }
+
+    pub fn cancel_owner_proposal(&mut self) -> bool {
+        self.assert_owner();
+        if self.pending_owner.take().is_none() {
+            panic!("No owner is proposed");
+        }
+        true
+    }
+
+    pub fn accept_ownership(&mut self) -> bool {
+        let account_id = env::predecessor_account_id();
+        if self.pending_owner.as_ref() != Some(&account_id) {
+            panic!("Only the proposed owner can accept the ownership.");
+        }
+
+        log!("Ownership is transferred to {}", account_id);
+        self.owner = self.pending_owner.take();
+        true
+    }
+
+    // Leaves the contract without owner for good. Roles can no longer be granted or revoked.
+    pub fn renounce_ownership(&mut self) -> bool {
+        self.assert_owner();
+        log!("Ownership is renounced");
+        self.owner = None;
+        self.pending_owner = None;
+        true
+    }
+
+    // null once the ownership is renounced
+    pub fn get_owner(&self) -> Option<&AccountId> {
+        self.owner.as_ref()
+    }
+
+    pub fn get_pending_owner(&self) -> Option<&AccountId> {
+        self.pending_owner.as_ref()
+    }
+}
@@ -0,0 +1,69 @@
+use near_sdk::near;
+use std::ops::Bound;
+
+// Upper bound for the `limit` accepted by every list view.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+#[near(serializers=[json])]
+pub struct Page<C, T> {
+    pub items: Vec<T>,
+    // pass it back as `cursor` to fetch the next page, null when there is nothing left
+    pub next_cursor: Option<C>,
+    pub max_page_size: u32,
+    // null when counting would mean scanning the whole collection
+    pub total: Option<u32>,
+}
+
+impl<C, T> Page<C, T> {
+    pub(crate) fn collect<I, F>(iter: I, limit: Option<u32>, total: u32, cursor_of: F) -> Self
+    where
+        I: Iterator<Item = T>,
+        F: Fn(&T) -> C,
+    {
+        Self {
+            total: Some(total),
+            ..Self::collect_untotaled(iter, limit, cursor_of)
+        }
+    }
+
+    // Reads the iterator up to the end of the page only.
+    pub(crate) fn collect_untotaled<I, F>(iter: I, limit: Option<u32>, cursor_of: F) -> Self
+    where
+        I: Iterator<Item = T>,
+        F: Fn(&T) -> C,
+    {
+        let limit = limit.unwrap_or(MAX_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+        let mut items: Vec<T> = iter.take(limit + 1).collect();
+
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(cursor_of)
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            next_cursor,
+            max_page_size: MAX_PAGE_SIZE,
+            total: None,
+        }
+    }
+}
+
+// Range bounds for the keys strictly before `cursor`, or all keys when there is no cursor.
+// Used by the views listing the newest entries first.
+pub(crate) fn before<K>(cursor: Option<K>) -> (Bound<K>, Bound<K>) {
+    match cursor {
+        Some(key) => (Bound::Unbounded, Bound::Excluded(key)),
+        None => (Bound::Unbounded, Bound::Unbounded),
+    }
+}
+
+// Range bounds for the keys strictly after `cursor`, or all keys when there is no cursor.
+pub(crate) fn after<K>(cursor: Option<K>) -> (Bound<K>, Bound<K>) {
+    match cursor {
+        Some(key) => (Bound::Excluded(key), Bound::Unbounded),
+        None => (Bound::Unbounded, Bound::Unbounded),
+    }
+}
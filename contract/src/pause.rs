@@ -0,0 +1,128 @@
+use crate::roles::ERole;
+use crate::{Contract, ContractExt, EProposalKind, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, log, near, AccountId};
+use std::collections::BTreeSet;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EPauseScope {
+    // new proposals and amendments
+    SUBMISSIONS,
+    // votes, rejections and withdrawals
+    VOTING,
+    EXECUTION,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct ContractPause {
+    paused_by: AccountId,
+    reason: String,
+    paused_at: U64,
+}
+
+// A scope is paused while its entry is set.
+#[near(serializers=[borsh, json])]
+#[derive(Default)]
+pub struct PauseStatus {
+    submissions: Option<ContractPause>,
+    voting: Option<ContractPause>,
+    execution: Option<ContractPause>,
+}
+
+impl PauseStatus {
+    fn scope_mut(&mut self, scope: EPauseScope) -> &mut Option<ContractPause> {
+        match scope {
+            EPauseScope::SUBMISSIONS => &mut self.submissions,
+            EPauseScope::VOTING => &mut self.voting,
+            EPauseScope::EXECUTION => &mut self.execution,
+        }
+    }
+
+    fn is_paused(&self, scope: EPauseScope) -> bool {
+        match scope {
+            EPauseScope::SUBMISSIONS => self.submissions.is_some(),
+            EPauseScope::VOTING => self.voting.is_some(),
+            EPauseScope::EXECUTION => self.execution.is_some(),
+        }
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_not_paused(&self, scope: EPauseScope) {
+        if self.pause_status.is_paused(scope) {
+            panic!("{:?} is paused.", scope);
+        }
+    }
+
+    // UNPAUSE proposals are exempt, voting on them is the only way to unpause.
+    pub(crate) fn assert_proposal_not_paused(&self, proposal: &ProposalInfo, scope: EPauseScope) {
+        if proposal.kind != EProposalKind::UNPAUSE {
+            self.assert_not_paused(scope);
+        }
+    }
+
+    pub(crate) fn finalize_unpause(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        let scopes = proposal
+            .pause_scopes
+            .clone()
+            .expect("Proposal is missing the pause scopes");
+
+        for scope in scopes {
+            if self.pause_status.scope_mut(scope).take().is_some() {
+                log!("{:?} is unpaused", scope);
+            }
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // The owner and emergency responders can pause, views stay available.
+    pub fn pause(&mut self, scopes: Vec<EPauseScope>, reason: String) -> bool {
+        self.assert_role(ERole::EMERGENCY);
+        if scopes.is_empty() {
+            panic!("Nothing to pause.");
+        }
+
+        for scope in scopes {
+            let pause = self.pause_status.scope_mut(scope);
+            if pause.is_none() {
+                log!("{:?} is paused: {}", scope, reason);
+                *pause = Some(ContractPause {
+                    paused_by: env::predecessor_account_id(),
+                    reason: reason.clone(),
+                    paused_at: U64(env::block_timestamp()),
+                });
+            }
+        }
+        true
+    }
+
+    // Guardians propose unpausing, the scopes are unpaused once the proposal is executed.
+    #[payable]
+    pub fn propose_unpause(&mut self, scopes: BTreeSet<EPauseScope>) -> String {
+        Contract::assert_proposal_deposit();
+        self.assert_guardian();
+        if scopes.is_empty() {
+            panic!("Nothing to unpause.");
+        }
+        for scope in scopes.iter() {
+            if !self.pause_status.is_paused(*scope) {
+                panic!("{:?} is not paused.", scope);
+            }
+        }
+
+        let mut proposal = ProposalInfo::without_project(EProposalKind::UNPAUSE);
+        proposal.pause_scopes = Some(scopes);
+
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+
+    pub fn get_pause_status(&self) -> &PauseStatus {
+        &self.pause_status
+    }
+}
@@ -0,0 +1,241 @@
+use crate::comments::MAX_COMMENT_LENGTH;
+use crate::denylist::MAX_EVIDENCE_URLS;
+use crate::pagination::{self, Page};
+use crate::pause::EPauseScope;
+use crate::roles::ERole;
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U64;
+use near_sdk::{env, log, near, AccountId, NearToken, Promise};
+
+// also the deposit of the REMOVE proposal a report can be escalated into
+pub const REPORT_BOND: NearToken = NearToken::from_near(1);
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EReportStatus {
+    // waiting in the triage queue
+    OPEN,
+    // the bond is returned
+    ACCEPTED,
+    // unfounded but in good faith, the bond is returned
+    DISMISSED,
+    // the bond is forfeited
+    FRIVOLOUS,
+}
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EReportEscalation {
+    SUSPENSION,
+    // the bond becomes the deposit of a REMOVE proposal submitted on behalf of the reporter
+    REMOVAL,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct ReportResolution {
+    resolved_by: AccountId,
+    note: String,
+    escalation: Option<EReportEscalation>,
+    // set when escalated into a REMOVE proposal
+    proposal_id: Option<String>,
+    resolved_at: U64,
+}
+
+#[near(serializers=[borsh, json])]
+pub struct CommunityReport {
+    id: u32,
+    reporter: AccountId,
+    // null when the whole project is reported
+    contract_id: Option<AccountId>,
+    project_id: String,
+    description: String,
+    evidence_urls: Vec<String>,
+    status: EReportStatus,
+    reported_at: U64,
+    resolution: Option<ReportResolution>,
+}
+
+impl Contract {
+    pub(crate) fn open_report_count(&self, project_id: &str) -> u32 {
+        *self.open_report_counts.get(project_id).unwrap_or(&0)
+    }
+
+    fn set_open_report_count(&mut self, project_id: &str, count: u32) {
+        if count == 0 {
+            self.open_report_counts.remove(project_id);
+        } else {
+            self.open_report_counts.insert(project_id.to_string(), count);
+        }
+    }
+
+    // Called when a project moves under another id, so its open reports follow it.
+    pub(crate) fn move_open_reports(&mut self, from_project_id: &str, to_project_id: &str) {
+        let count = self.open_report_count(from_project_id);
+        if count > 0 {
+            self.set_open_report_count(from_project_id, 0);
+            self.set_open_report_count(to_project_id, self.open_report_count(to_project_id) + count);
+        }
+    }
+
+    fn reported_project_id(&self, contract_id: &Option<AccountId>, project_id: Option<String>) -> String {
+        match (contract_id, project_id) {
+            (Some(contract_id), None) => self
+                .contract_project_index
+                .get(contract_id)
+                .unwrap_or_else(|| panic!("{} is not whitelisted", contract_id))
+                .clone(),
+            (None, Some(project_id)) => {
+                let project_id = self.current_project_id(&project_id).to_string();
+                if !self.approved_projects.contains_key(&project_id) {
+                    panic!("Project not found");
+                }
+                project_id
+            }
+            _ => panic!("Report either a contract id or a project id"),
+        }
+    }
+
+    // Submits a REMOVE proposal for the project, paid with the reporter's bond.
+    fn escalate_to_removal(&mut self, report_id: u32, project_id: &str) -> String {
+        let report = self.reports.get(&report_id).expect("Report not found");
+        let mut proposal = self.remove_project_proposal(project_id);
+        proposal.proposed_by = report.reporter.clone();
+
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+}
+
+#[near]
+impl Contract {
+    // Reports a whitelisted contract or an approved project to the guardians, with a bond of 1 NEAR.
+    #[payable]
+    pub fn report(
+        &mut self,
+        contract_id: Option<AccountId>,
+        project_id: Option<String>,
+        description: String,
+        evidence_urls: Vec<String>,
+    ) -> u32 {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        if env::attached_deposit() != REPORT_BOND {
+            panic!("Reporting requires a bond of 1 NEAR.");
+        }
+        if description.is_empty() || description.chars().count() > MAX_COMMENT_LENGTH {
+            panic!("Description must be between 1 and {} characters", MAX_COMMENT_LENGTH);
+        }
+        if evidence_urls.is_empty() || evidence_urls.len() > MAX_EVIDENCE_URLS {
+            panic!("Between 1 and {} evidence links are required", MAX_EVIDENCE_URLS);
+        }
+
+        let project_id = self.reported_project_id(&contract_id, project_id);
+        self.report_count += 1;
+        let id = self.report_count;
+        self.reports.insert(
+            id,
+            CommunityReport {
+                id,
+                reporter: env::predecessor_account_id(),
+                contract_id,
+                project_id: project_id.clone(),
+                description,
+                evidence_urls,
+                status: EReportStatus::OPEN,
+                reported_at: U64(env::block_timestamp()),
+                resolution: None,
+            },
+        );
+        self.set_open_report_count(&project_id, self.open_report_count(&project_id) + 1);
+        self.report_queue.insert(id, ());
+        id
+    }
+
+    // Guardians close an open report. Only accepted reports can be escalated, a suspension
+    // also takes the EMERGENCY role.
+    pub fn resolve_report(
+        &mut self,
+        report_id: u32,
+        status: EReportStatus,
+        note: String,
+        escalation: Option<EReportEscalation>,
+    ) -> bool {
+        self.assert_guardian();
+        if !self.report_queue.contains_key(&report_id) {
+            panic!("Report is not open");
+        }
+        if status == EReportStatus::OPEN {
+            panic!("A report can't be resolved as OPEN");
+        }
+        if escalation.is_some() && status != EReportStatus::ACCEPTED {
+            panic!("Only accepted reports can be escalated");
+        }
+        if escalation == Some(EReportEscalation::SUSPENSION) {
+            self.assert_role(ERole::EMERGENCY);
+        }
+
+        self.report_queue.remove(&report_id);
+        let report = self.reports.get(&report_id).expect("Report not found");
+        let reporter = report.reporter.clone();
+        // the project may have been renamed or merged since it was reported
+        let project_id = self.current_project_id(&report.project_id).to_string();
+        self.set_open_report_count(&project_id, self.open_report_count(&project_id).saturating_sub(1));
+        let mut proposal_id = None;
+        match escalation {
+            Some(EReportEscalation::SUSPENSION) => {
+                if !self.is_project_suspended(&project_id) {
+                    self.suspend_project(project_id, format!("Report {}: {}", report_id, note));
+                }
+                Promise::new(reporter.clone()).transfer(REPORT_BOND);
+                log!("Returned the bond of report {} to {}", report_id, reporter);
+            }
+            Some(EReportEscalation::REMOVAL) => {
+                proposal_id = Some(self.escalate_to_removal(report_id, &project_id));
+            }
+            None => {
+                if status == EReportStatus::FRIVOLOUS {
+                    log!("Bond of report {} is forfeited", report_id);
+                } else {
+                    Promise::new(reporter.clone()).transfer(REPORT_BOND);
+                    log!("Returned the bond of report {} to {}", report_id, reporter);
+                }
+            }
+        }
+
+        let report = self.reports.get_mut(&report_id).unwrap();
+        report.status = status;
+        report.resolution = Some(ReportResolution {
+            resolved_by: env::predecessor_account_id(),
+            note,
+            escalation,
+            proposal_id,
+            resolved_at: U64(env::block_timestamp()),
+        });
+        true
+    }
+
+    pub fn get_report(&self, report_id: u32) -> Option<&CommunityReport> {
+        self.reports.get(&report_id)
+    }
+
+    // Open reports, oldest first.
+    pub fn list_report_queue(&self, cursor: Option<u32>, limit: Option<u32>) -> Page<u32, &CommunityReport> {
+        Page::collect(
+            self.report_queue
+                .range(pagination::after(cursor))
+                .filter_map(|(report_id, _)| self.reports.get(report_id)),
+            limit,
+            self.report_queue.len(),
+            |report| report.id,
+        )
+    }
+
+    pub fn list_reports(&self, cursor: Option<u32>, limit: Option<u32>) -> Page<u32, &CommunityReport> {
+        Page::collect(
+            self.reports.range(pagination::after(cursor)).map(|(_, report)| report),
+            limit,
+            self.reports.len(),
+            |report| report.id,
+        )
+    }
+}
@@ -0,0 +1,419 @@
+use crate::pause::EPauseScope;
+use crate::{Contract, ContractExt, EProposalKind, ProjectInfo, ProjectMetadata, ProposalInfo};
+use near_sdk::{log, near, serde_json, AccountId};
+use std::collections::HashSet;
+
+impl Contract {
+    fn approved_project(&self, project_id: &str) -> &ProjectInfo {
+        self.approved_projects
+            .get(project_id)
+            .unwrap_or_else(|| panic!("Project {} not found", project_id))
+    }
+
+    // Retired ids stay reserved, so they keep resolving to their successor. Removed ids too,
+    // as merged or renamed ids may still resolve to them.
+    pub(crate) fn is_project_id_taken(&self, project_id: &str) -> bool {
+        self.approved_projects.contains_key(project_id)
+            || self.project_redirects.contains_key(project_id)
+            || self.removed_project_ids.contains(project_id)
+    }
+
+    pub(crate) fn current_project_id<'a>(&'a self, project_id: &'a str) -> &'a str {
+        let mut current = project_id;
+        while !self.approved_projects.contains_key(current) {
+            match self.project_redirects.get(current) {
+                Some(successor) => current = successor,
+                None => break,
+            }
+        }
+        current
+    }
+
+    fn proposal_projects(&self, proposal_id: &str) -> (String, ProjectInfo) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        let related_project = proposal
+            .related_project
+            .clone()
+            .expect("Proposal is missing the related project");
+        (proposal.target_project_id().to_string(), related_project)
+    }
+
+    // Moves the records of the candidate contracts the execution indexed under the other project.
+    fn move_project_records(&mut self, from_project_id: &str, to_project_id: &str, candidate_ids: &HashSet<AccountId>) {
+        let moved_contract_ids: Vec<AccountId> = self
+            .approved_project(from_project_id)
+            .contract_ids
+            .iter()
+            .filter(|contract_id| {
+                candidate_ids.contains(*contract_id)
+                    && self.contract_project_index.get(*contract_id).map(String::as_str) == Some(to_project_id)
+            })
+            .cloned()
+            .collect();
+
+        if let Some(from_project) = self.approved_projects.get_mut(from_project_id) {
+            for contract_id in moved_contract_ids.iter() {
+                from_project.contract_ids.remove(contract_id);
+            }
+        }
+        if let Some(to_project) = self.approved_projects.get_mut(to_project_id) {
+            to_project.contract_ids.extend(moved_contract_ids);
+        }
+    }
+
+    pub(crate) fn finalize_transfer(&mut self, proposal_id: &str) {
+        let (from_project_id, to_project) = self.proposal_projects(proposal_id);
+
+        if !self.approved_projects.contains_key(&from_project_id)
+            || !self.approved_projects.contains_key(&to_project.project_id)
+        {
+            log!("Project not found for the proposal, and no transfer will be performed");
+            return;
+        }
+
+        self.move_project_records(&from_project_id, &to_project.project_id, &to_project.contract_ids);
+
+        self.record_project_version(&from_project_id, Some(proposal_id.to_string()));
+        self.record_project_version(&to_project.project_id, Some(proposal_id.to_string()));
+    }
+
+    // Moves every contract of the retired project to its successor and retires the project id.
+    pub(crate) fn finalize_merge(&mut self, proposal_id: &str) {
+        let (retired_project_id, successor) = self.proposal_projects(proposal_id);
+
+        if !self.approved_projects.contains_key(&retired_project_id)
+            || !self.approved_projects.contains_key(&successor.project_id)
+        {
+            log!("Project not found for the proposal, and no merge will be performed");
+            return;
+        }
+
+        let retired_contract_ids = self.approved_project(&retired_project_id).contract_ids.clone();
+        self.move_project_records(&retired_project_id, &successor.project_id, &retired_contract_ids);
+        if let Some(successor_project) = self.approved_projects.get_mut(&successor.project_id) {
+            successor_project.metadata = successor.metadata;
+        }
+
+        // the last version of the retired project is left empty
+        self.record_project_version(&retired_project_id, Some(proposal_id.to_string()));
+        self.record_project_version(&successor.project_id, Some(proposal_id.to_string()));
+
+        self.approved_projects.remove(&retired_project_id);
+        self.suspended_projects.remove(&retired_project_id);
+        self.move_open_reports(&retired_project_id, &successor.project_id);
+        self.project_redirects
+            .insert(retired_project_id, successor.project_id);
+    }
+
+    // Creates the new project of a SPLIT proposal with the contracts moved out of the source project.
+    pub(crate) fn finalize_split(&mut self, proposal_id: &str) {
+        let (source_project_id, new_project) = self.proposal_projects(proposal_id);
+
+        if !self.approved_projects.contains_key(&source_project_id) {
+            log!("Project not found for the proposal, and no split will be performed");
+            return;
+        }
+        if self.is_project_id_taken(&new_project.project_id) {
+            log!("Skipping split project as the project id is occupied.");
+            return;
+        }
+
+        let mut pending_proposals = self.pending_proposal_ids(&new_project.project_id);
+        pending_proposals.remove(proposal_id);
+        self.approved_projects.insert(
+            new_project.project_id.clone(),
+            ProjectInfo {
+                contract_ids: HashSet::new(),
+                metadata: new_project.metadata,
+                pending_proposals,
+                project_id: new_project.project_id.clone(),
+            },
+        );
+        self.move_project_records(&source_project_id, &new_project.project_id, &new_project.contract_ids);
+
+        self.record_project_version(&source_project_id, Some(proposal_id.to_string()));
+        self.record_project_version(&new_project.project_id, Some(proposal_id.to_string()));
+    }
+
+    // Moves the project under its new id. Pending proposals targeting the old id follow it,
+    // versions recorded before the rename stay under the old id.
+    pub(crate) fn finalize_rename(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        let old_project_id = proposal.target_project_id().to_string();
+        let new_project_id = proposal.project_info.project_id.clone();
+
+        if !self.approved_projects.contains_key(&old_project_id) {
+            log!("Project not found for the proposal, and no rename will be performed");
+            return;
+        }
+        if self.is_project_id_taken(&new_project_id) {
+            log!("Skipping rename project as the project id is occupied.");
+            return;
+        }
+
+        let mut project = self.approved_projects.remove(&old_project_id).unwrap();
+        project.project_id = new_project_id.clone();
+        project.pending_proposals = HashSet::new();
+        self.approved_projects.insert(new_project_id.clone(), project);
+        if let Some(suspension) = self.suspended_projects.remove(&old_project_id) {
+            self.suspended_projects.insert(new_project_id.clone(), suspension);
+        }
+        self.move_open_reports(&old_project_id, &new_project_id);
+        self.project_redirects
+            .insert(old_project_id.clone(), new_project_id.clone());
+
+        self.record_project_version(&new_project_id, Some(proposal_id.to_string()));
+
+        for pending_proposal_id in self.pending_proposal_ids(&old_project_id) {
+            if pending_proposal_id != proposal_id {
+                self.retarget_proposal(&pending_proposal_id, &old_project_id, &new_project_id);
+            }
+        }
+    }
+
+    // Removes the project once every contract is unindexed. Its id stays reserved.
+    pub(crate) fn finalize_remove(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        let project_id = proposal.target_project_id().to_string();
+
+        match self.approved_projects.get_mut(&project_id) {
+            None => {
+                log!("Project not found for the proposal, and no removal will be performed")
+            }
+            Some(project) => {
+                // the last version of the removed project is left empty
+                project.contract_ids.clear();
+                self.record_project_version(&project_id, Some(proposal_id.to_string()));
+                self.approved_projects.remove(&project_id);
+                self.suspended_projects.remove(&project_id);
+                self.removed_project_ids.insert(project_id);
+            }
+        }
+    }
+
+    // The proposal holds the project without its contracts, as it is once removed.
+    pub(crate) fn remove_project_proposal(&self, project_id: &str) -> ProposalInfo {
+        let project = self.approved_project(project_id);
+        ProposalInfo::new(
+            EProposalKind::REMOVE,
+            Some(project_id.to_string()),
+            ProjectInfo {
+                contract_ids: HashSet::new(),
+                metadata: project.metadata.clone(),
+                pending_proposals: HashSet::new(),
+                project_id: project_id.to_string(),
+            },
+        )
+    }
+
+    fn retarget_proposal(&mut self, proposal_id: &str, old_project_id: &str, new_project_id: &str) {
+        let mut proposal = self.proposals.remove(proposal_id).expect("Proposal not found");
+        // a NEW proposal for the old id proposes another project
+        if proposal.kind == EProposalKind::NEW {
+            self.proposals.insert(proposal_id.to_string(), proposal);
+            return;
+        }
+        self.unindex_proposal(proposal_id, &proposal);
+        // the project keeps its content, only its id changes
+        proposal.base_versions.remove(old_project_id);
+
+        let project_ids = [
+            proposal.project_id.as_mut(),
+            Some(&mut proposal.project_info.project_id),
+            proposal.related_project.as_mut().map(|project| &mut project.project_id),
+        ];
+        for project_id in project_ids.into_iter().flatten() {
+            if project_id == old_project_id {
+                *project_id = new_project_id.to_string();
+            }
+        }
+
+        self.insert_proposal(proposal_id.to_string(), proposal);
+    }
+
+    fn insert_restructure_proposal(
+        &mut self,
+        kind: EProposalKind,
+        project_info: ProjectInfo,
+        related_project: ProjectInfo,
+    ) -> String {
+        let mut proposal = ProposalInfo::new(kind, Some(project_info.project_id.clone()), project_info);
+        proposal.related_project = Some(related_project);
+
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+
+    fn assert_owned_contracts(&self, project_id: &str, contract_ids: &HashSet<AccountId>) {
+        if contract_ids.is_empty() {
+            panic!("No contract to move");
+        }
+        let project = self.approved_project(project_id);
+        for contract_id in contract_ids.iter() {
+            if !project.contract_ids.contains(contract_id) {
+                panic!("{} does not belong to {}", contract_id, project_id);
+            }
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    // Proposes moving contracts from one approved project to another.
+    // The proposal holds both projects as they will look once it is approved.
+    #[payable]
+    pub fn transfer_contracts(
+        &mut self,
+        from_project_id: String,
+        to_project_id: String,
+        contract_ids: HashSet<AccountId>,
+    ) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        if from_project_id == to_project_id {
+            panic!("Cannot transfer contracts to the same project");
+        }
+        self.assert_owned_contracts(&from_project_id, &contract_ids);
+
+        let from_project = self.approved_project(&from_project_id);
+        let to_project = self.approved_project(&to_project_id);
+        let project_info = ProjectInfo {
+            contract_ids: from_project.contract_ids.difference(&contract_ids).cloned().collect(),
+            metadata: from_project.metadata.clone(),
+            pending_proposals: HashSet::new(),
+            project_id: from_project_id,
+        };
+        let related_project = ProjectInfo {
+            contract_ids: to_project.contract_ids.union(&contract_ids).cloned().collect(),
+            metadata: to_project.metadata.clone(),
+            pending_proposals: HashSet::new(),
+            project_id: to_project_id,
+        };
+
+        self.insert_restructure_proposal(EProposalKind::TRANSFER, project_info, related_project)
+    }
+
+    // Proposes merging a project into another one, with the metadata the merged project will have.
+    // Once approved, the id of the retired project resolves to the project it was merged into.
+    #[payable]
+    pub fn merge_projects(&mut self, project_id: String, into_project_id: String, metadata: String) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        if project_id == into_project_id {
+            panic!("Cannot merge a project into itself");
+        }
+        serde_json::from_str::<ProjectMetadata>(&metadata).expect("Incorrect metadata structure");
+
+        let retired_project = self.approved_project(&project_id);
+        let successor = self.approved_project(&into_project_id);
+        let project_info = ProjectInfo {
+            contract_ids: HashSet::new(),
+            metadata: retired_project.metadata.clone(),
+            pending_proposals: HashSet::new(),
+            project_id,
+        };
+        let related_project = ProjectInfo {
+            contract_ids: successor
+                .contract_ids
+                .union(&retired_project.contract_ids)
+                .cloned()
+                .collect(),
+            metadata,
+            pending_proposals: HashSet::new(),
+            project_id: into_project_id,
+        };
+
+        self.insert_restructure_proposal(EProposalKind::MERGE, project_info, related_project)
+    }
+
+    // Proposes moving some contracts of a project into a new project.
+    #[payable]
+    pub fn split_project(
+        &mut self,
+        project_id: String,
+        new_project_id: String,
+        contract_ids: HashSet<AccountId>,
+        metadata: String,
+    ) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        if new_project_id.is_empty() {
+            panic!("Project id length must be greater than 0")
+        }
+        if self.is_project_id_taken(&new_project_id) {
+            panic!("Project id is occupied, please use another project id.")
+        }
+        serde_json::from_str::<ProjectMetadata>(&metadata).expect("Incorrect metadata structure");
+        self.assert_owned_contracts(&project_id, &contract_ids);
+
+        let source_project = self.approved_project(&project_id);
+        let project_info = ProjectInfo {
+            contract_ids: source_project.contract_ids.difference(&contract_ids).cloned().collect(),
+            metadata: source_project.metadata.clone(),
+            pending_proposals: HashSet::new(),
+            project_id,
+        };
+        let related_project = ProjectInfo {
+            contract_ids,
+            metadata,
+            pending_proposals: HashSet::new(),
+            project_id: new_project_id,
+        };
+
+        self.insert_restructure_proposal(EProposalKind::SPLIT, project_info, related_project)
+    }
+
+    // Proposes moving a project under a new id, the old id keeps resolving to it.
+    #[payable]
+    pub fn rename_project(&mut self, project_id: String, new_project_id: String) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        if new_project_id.is_empty() {
+            panic!("Project id length must be greater than 0")
+        }
+        if self.is_project_id_taken(&new_project_id) {
+            panic!("Project id is occupied, please use another project id.")
+        }
+
+        let project = self.approved_project(&project_id);
+        let proposal = ProposalInfo::new(
+            EProposalKind::RENAME,
+            Some(project_id),
+            ProjectInfo {
+                contract_ids: project.contract_ids.clone(),
+                metadata: project.metadata.clone(),
+                pending_proposals: HashSet::new(),
+                project_id: new_project_id,
+            },
+        );
+
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+
+    // Proposes removing a project, its contracts are no longer whitelisted once approved.
+    #[payable]
+    pub fn remove_project(&mut self, project_id: String) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+
+        let proposal = self.remove_project_proposal(&project_id);
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+
+    // Current id of a project, following merges and renames. Null if the project does not exist.
+    pub fn resolve_project_id(&self, project_id: String) -> Option<String> {
+        let current_project_id = self.current_project_id(&project_id);
+        self.approved_projects
+            .contains_key(current_project_id)
+            .then(|| current_project_id.to_string())
+    }
+}
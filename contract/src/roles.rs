@@ -0,0 +1,102 @@
+use crate::{Contract, ContractExt};
+use near_sdk::{env, near, AccountId};
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ERole {
+    // manages the config
+    ADMIN,
+    // votes on proposals
+    GUARDIAN,
+    // suspends projects
+    EMERGENCY,
+    // edits project metadata
+    CURATOR,
+}
+
+const ROLES: [ERole; 4] = [ERole::ADMIN, ERole::GUARDIAN, ERole::EMERGENCY, ERole::CURATOR];
+
+impl Contract {
+    // Guardians keep living in their own set, the other roles in role_members.
+    pub(crate) fn is_role_member(&self, account_id: &AccountId, role: ERole) -> bool {
+        match role {
+            ERole::GUARDIAN => self.guardians.contains(account_id),
+            _ => self
+                .role_members
+                .get(&role)
+                .is_some_and(|members| members.contains(account_id)),
+        }
+    }
+
+    // The owner can act with every role but GUARDIAN.
+    pub(crate) fn assert_role(&self, role: ERole) {
+        let account_id = env::predecessor_account_id();
+        if !self.is_owner(&account_id) && !self.is_role_member(&account_id, role) {
+            panic!("{} does not have the {:?} role", account_id, role);
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    pub fn grant_role(&mut self, account_id: AccountId, role: ERole) {
+        self.assert_owner();
+        match role {
+            ERole::GUARDIAN => {
+                self.guardians.insert(account_id);
+            }
+            _ => {
+                self.role_members.entry(role).or_default().insert(account_id);
+            }
+        }
+    }
+
+    // Revoking a guardian also drops their votes on pending proposals, including the ones
+    // they cast as a delegate, and every delegation from or to them. It fails if the remaining
+    // guardians could no longer reach the vote threshold.
+    pub fn revoke_role(&mut self, account_id: AccountId, role: ERole) {
+        self.assert_owner();
+        match role {
+            ERole::GUARDIAN => {
+                if self.guardians.remove(&account_id) {
+                    for (_, info) in self.proposals.iter_mut() {
+                        info.remove_guardian_votes(&account_id);
+                    }
+                    self.remove_guardian_delegations(&account_id);
+                    self.guardian_weights.remove(&account_id);
+                    self.assert_vote_threshold_reachable();
+                    self.refresh_tallies();
+                }
+            }
+            _ => {
+                if let Some(members) = self.role_members.get_mut(&role) {
+                    members.remove(&account_id);
+                }
+            }
+        }
+    }
+
+    pub fn has_role(&self, account_id: AccountId, role: ERole) -> bool {
+        self.is_role_member(&account_id, role)
+    }
+
+    pub fn list_role_members(&self, role: ERole) -> Vec<&AccountId> {
+        let mut members: Vec<&AccountId> = match role {
+            ERole::GUARDIAN => self.guardians.iter().collect(),
+            _ => self
+                .role_members
+                .get(&role)
+                .map(|members| members.iter().collect())
+                .unwrap_or_default(),
+        };
+        members.sort();
+        members
+    }
+
+    pub fn get_account_roles(&self, account_id: AccountId) -> Vec<ERole> {
+        ROLES
+            .into_iter()
+            .filter(|role| self.is_role_member(&account_id, *role))
+            .collect()
+    }
+}
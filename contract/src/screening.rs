@@ -0,0 +1,131 @@
+use crate::{Contract, ContractExt, EProposalKind, ProjectMetadata};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, serde_json, AccountId};
+
+#[near(serializers=[json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EScreeningVerdict {
+    TRUSTED,
+    UNKNOWN,
+    // whitelisted, but its project is suspended
+    SUSPENDED,
+    DENYLISTED,
+}
+
+#[near(serializers=[json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EProjectTier {
+    // has an audit report that has not expired
+    AUDITED,
+    LISTED,
+}
+
+#[near(serializers=[json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum EScreeningWarning {
+    AUDIT_EXPIRED,
+    // a REMOVE proposal targets the project
+    PENDING_REMOVAL,
+    // the project is reported and the report is not triaged yet
+    OPEN_REPORTS,
+    // a deposit is attached for a receiver that is not trusted
+    DEPOSIT_TO_UNTRUSTED,
+    // best effort, from the method name only: `*_approve` methods such as nft_approve usually
+    // hand control of tokens to an account that is not screened here
+    TOKEN_APPROVAL,
+}
+
+#[near(serializers=[json])]
+pub struct ScreeningResult {
+    verdict: EScreeningVerdict,
+    // null when the receiver is not whitelisted
+    project_id: Option<String>,
+    tier: Option<EProjectTier>,
+    warnings: Vec<EScreeningWarning>,
+}
+
+impl Contract {
+    // Denylisting takes precedence over the whitelist, then suspensions.
+    fn screening_verdict(&self, receiver_id: &AccountId, project_id: Option<&String>) -> EScreeningVerdict {
+        if self.is_denylisted(receiver_id) {
+            return EScreeningVerdict::DENYLISTED;
+        }
+        match project_id {
+            Some(project_id) if self.is_project_suspended(project_id) => EScreeningVerdict::SUSPENDED,
+            Some(_) => EScreeningVerdict::TRUSTED,
+            None => EScreeningVerdict::UNKNOWN,
+        }
+    }
+
+    fn project_warnings(&self, project_id: &str) -> Vec<EScreeningWarning> {
+        let mut warnings = Vec::new();
+        let is_removal_pending = self.pending_proposal_ids(project_id).iter().any(|proposal_id| {
+            self.proposals.get(proposal_id).is_some_and(|proposal| {
+                proposal.kind == EProposalKind::REMOVE && proposal.target_project_id() == project_id
+            })
+        });
+        if is_removal_pending {
+            warnings.push(EScreeningWarning::PENDING_REMOVAL);
+        }
+        if self.open_report_count(project_id) > 0 {
+            warnings.push(EScreeningWarning::OPEN_REPORTS);
+        }
+        warnings
+    }
+}
+
+#[near]
+impl Contract {
+    // Screens a function call a wallet is about to sign, in a single view.
+    pub fn screen_transaction(
+        &self,
+        receiver_id: AccountId,
+        method_name: String,
+        attached_deposit: U128,
+    ) -> ScreeningResult {
+        let project_id = self.contract_project_index.get(&receiver_id);
+        let verdict = self.screening_verdict(&receiver_id, project_id);
+        let mut warnings = Vec::new();
+
+        let mut tier = None;
+        if let Some(project_id) = project_id {
+            let metadata = self
+                .approved_projects
+                .get(project_id)
+                .and_then(|project| serde_json::from_str::<ProjectMetadata>(&project.metadata).ok());
+            let is_audited = metadata
+                .as_ref()
+                .and_then(|metadata| metadata.audit_report_url.as_ref())
+                .is_some_and(|audit_report_url| !audit_report_url.is_empty());
+            let is_audit_expired = metadata
+                .and_then(|metadata| metadata.audit_expires_at)
+                .and_then(|audit_expires_at| audit_expires_at.parse::<u64>().ok())
+                .is_some_and(|audit_expires_at| env::block_timestamp() >= audit_expires_at);
+
+            if is_audit_expired {
+                warnings.push(EScreeningWarning::AUDIT_EXPIRED);
+            }
+            tier = Some(if is_audited && !is_audit_expired {
+                EProjectTier::AUDITED
+            } else {
+                EProjectTier::LISTED
+            });
+            warnings.extend(self.project_warnings(project_id));
+        }
+
+        if attached_deposit.0 > 0 && verdict != EScreeningVerdict::TRUSTED {
+            warnings.push(EScreeningWarning::DEPOSIT_TO_UNTRUSTED);
+        }
+        if method_name.ends_with("_approve") {
+            warnings.push(EScreeningWarning::TOKEN_APPROVAL);
+        }
+
+        ScreeningResult {
+            verdict,
+            project_id: project_id.cloned(),
+            tier,
+            warnings,
+        }
+    }
+}
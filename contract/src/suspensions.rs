@@ -0,0 +1,71 @@
+use crate::pagination::{self, Page};
+use crate::roles::ERole;
+use crate::{Contract, ContractExt};
+use near_sdk::json_types::U64;
+use near_sdk::{env, log, near, AccountId};
+
+#[near(serializers=[borsh, json])]
+pub struct ProjectSuspension {
+    suspended_by: AccountId,
+    reason: String,
+    suspended_at: U64,
+}
+
+impl Contract {
+    pub(crate) fn is_project_suspended(&self, project_id: &str) -> bool {
+        self.suspended_projects.contains_key(project_id)
+    }
+}
+
+#[near]
+impl Contract {
+    // Contracts of a suspended project are not reported as whitelisted until the suspension is lifted.
+    pub fn suspend_project(&mut self, project_id: String, reason: String) -> bool {
+        self.assert_role(ERole::EMERGENCY);
+
+        if !self.approved_projects.contains_key(&project_id) {
+            panic!("Project not found");
+        }
+        if self.is_project_suspended(&project_id) {
+            panic!("Project is suspended already");
+        }
+
+        log!("Project {} is suspended: {}", project_id, reason);
+        self.suspended_projects.insert(
+            project_id,
+            ProjectSuspension {
+                suspended_by: env::predecessor_account_id(),
+                reason,
+                suspended_at: U64(env::block_timestamp()),
+            },
+        );
+        true
+    }
+
+    pub fn lift_project_suspension(&mut self, project_id: String) -> bool {
+        self.assert_role(ERole::ADMIN);
+
+        if self.suspended_projects.remove(&project_id).is_none() {
+            panic!("Project is not suspended");
+        }
+        log!("Suspension of project {} is lifted", project_id);
+        true
+    }
+
+    pub fn get_project_suspension(&self, project_id: String) -> Option<&ProjectSuspension> {
+        self.suspended_projects.get(&project_id)
+    }
+
+    pub fn list_suspended_projects(
+        &self,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Page<String, (&String, &ProjectSuspension)> {
+        Page::collect(
+            self.suspended_projects.range(pagination::after(cursor)),
+            limit,
+            self.suspended_projects.len(),
+            |(project_id, _)| (*project_id).clone(),
+        )
+    }
+}
@@ -0,0 +1,62 @@
+use crate::pause::EPauseScope;
+use crate::roles::ERole;
+use crate::{Contract, ContractExt, EProposalStatus, ProposalInfo};
+use near_sdk::json_types::U64;
+use near_sdk::{env, log, near};
+
+impl ProposalInfo {
+    pub(crate) fn assert_open_for_voting(&self) {
+        if self.status != EProposalStatus::PENDING {
+            panic!("Proposal is not open for voting.");
+        }
+    }
+}
+
+impl Contract {
+    // Called once the approval weight reaches the threshold. The proposal waits for the execution
+    // delay, during which it can be vetoed, then anyone can execute it.
+    pub(crate) fn pass_proposal(&mut self, proposal_id: &str) {
+        let executable_at = env::block_timestamp().saturating_add(self.config.execution_delay.0);
+        let proposal = self.proposals.get_mut(proposal_id).expect("Proposal not found");
+        proposal.status = EProposalStatus::PASSED;
+        proposal.executable_at = Some(U64(executable_at));
+
+        log!("Proposal {} passed, it can be executed from {}", proposal_id, executable_at);
+    }
+}
+
+#[near]
+impl Contract {
+    // Anyone can execute a passed proposal once its execution delay is over. Large proposals are
+    // executed in chunks, returns false while more calls are needed.
+    pub fn execute_proposal(&mut self, proposal_id: String) -> bool {
+        let proposal = self.proposals.get(&proposal_id).expect("Proposal not found");
+        if proposal.status != EProposalStatus::PASSED {
+            panic!("Proposal has not passed.");
+        }
+        if proposal.executable_at.is_some_and(|executable_at| env::block_timestamp() < executable_at.0) {
+            panic!("Proposal is still in its execution delay.");
+        }
+        self.assert_proposal_not_paused(proposal, EPauseScope::EXECUTION);
+
+        self.execute_chunk(&proposal_id)
+    }
+
+    // Emergency responders can stop a passed proposal during its execution delay. The deposit is kept.
+    pub fn veto_proposal(&mut self, proposal_id: String, reason: String) -> bool {
+        self.assert_role(ERole::EMERGENCY);
+
+        let proposal = self.proposals.get(&proposal_id).expect("Proposal not found");
+        if proposal.status != EProposalStatus::PASSED {
+            panic!("Only passed proposals can be vetoed.");
+        }
+        // execution can only start once the delay is over, so an executing proposal is never vetoed
+        if proposal.executable_at.is_some_and(|executable_at| env::block_timestamp() >= executable_at.0) {
+            panic!("The execution delay is over, the proposal can no longer be vetoed.");
+        }
+
+        log!("Proposal {} is vetoed: {}", proposal_id, reason);
+        self.archive_proposal(&proposal_id, EProposalStatus::VETOED);
+        true
+    }
+}
@@ -0,0 +1,189 @@
+use crate::pause::EPauseScope;
+use crate::{Contract, ContractExt, EProposalKind, ProposalInfo};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8};
+use near_sdk::{env, log, near, AccountId, CryptoHash, Gas, GasWeight, NearToken, Promise};
+use std::collections::BTreeMap;
+
+// called on the new code when the proposal does not name a migration method
+const DEFAULT_MIGRATE_METHOD: &str = "migrate";
+// 4 MiB, the largest contract NEAR deploys, shared by every staged code hash
+pub const MAX_STAGED_CODE_SIZE: u32 = 4 * 1024 * 1024;
+
+#[near(serializers=[borsh, json])]
+#[derive(Clone)]
+pub struct CodeUpgrade {
+    // sha256 of the wasm, which must be staged with stage_code before execution
+    code_hash: Base58CryptoHash,
+    // called without arguments right after the code is deployed
+    migrate_method: String,
+}
+
+// Code staged for an upgrade, with the storage deposit of every guardian who staged a chunk.
+#[near(serializers=[borsh])]
+pub struct CodeStaging {
+    code: Vec<u8>,
+    storage_deposits: BTreeMap<AccountId, NearToken>,
+}
+
+#[near(serializers=[json])]
+pub struct StagedCode {
+    size: u32,
+    // whether the staged bytes match the code hash, meaning every chunk was staged
+    complete: bool,
+}
+
+fn matches_hash(code: &[u8], code_hash: Base58CryptoHash) -> bool {
+    env::sha256_array(code) == CryptoHash::from(code_hash)
+}
+
+impl Contract {
+    pub(crate) fn assert_guardian(&self) {
+        if !self.guardians.contains(&env::predecessor_account_id()) {
+            panic!("You are not a qualified guardian");
+        }
+    }
+
+    // Only code proposed by a pending or passed upgrade can be staged.
+    fn is_code_hash_proposed(&self, code_hash: Base58CryptoHash) -> bool {
+        self.proposals
+            .values()
+            .any(|proposal| proposal.upgrade.as_ref().is_some_and(|upgrade| upgrade.code_hash == code_hash))
+    }
+
+    // Deletes the staged code and refunds the storage deposits.
+    fn remove_staged_code(&mut self, code_hash: Base58CryptoHash) -> Option<Vec<u8>> {
+        let staging = self.staged_code.remove(&code_hash)?;
+        self.staged_code_size -= staging.code.len() as u32;
+        for (account_id, deposit) in staging.storage_deposits {
+            Promise::new(account_id).transfer(deposit);
+        }
+        Some(staging.code)
+    }
+
+    // A finalized upgrade proposal releases its staged code, unless another proposal still needs it.
+    pub(crate) fn release_staged_code(&mut self, proposal: &ProposalInfo) {
+        if let Some(upgrade) = &proposal.upgrade {
+            if !self.is_code_hash_proposed(upgrade.code_hash) {
+                self.remove_staged_code(upgrade.code_hash);
+            }
+        }
+    }
+
+    // Deploys the staged code to the contract account and runs its migration in the same batch,
+    // so a failing migration also reverts the deployment.
+    pub(crate) fn finalize_upgrade(&mut self, proposal_id: &str) {
+        let proposal = self.proposals.get(proposal_id).expect("Proposal not found");
+        let upgrade = proposal
+            .upgrade
+            .clone()
+            .expect("Proposal is missing the code upgrade");
+
+        let is_staged = self
+            .staged_code
+            .get(&upgrade.code_hash)
+            .is_some_and(|staging| matches_hash(&staging.code, upgrade.code_hash));
+        if !is_staged {
+            panic!("Code is not fully staged.");
+        }
+        let code = self.remove_staged_code(upgrade.code_hash).unwrap();
+
+        log!(
+            "Deploying code {} and calling {}",
+            String::from(&upgrade.code_hash),
+            upgrade.migrate_method
+        );
+        Promise::new(env::current_account_id())
+            .deploy_contract(code)
+            .function_call_weight(
+                upgrade.migrate_method,
+                Vec::new(),
+                NearToken::from_yoctonear(0),
+                Gas::from_gas(0),
+                GasWeight(1),
+            );
+    }
+}
+
+#[near]
+impl Contract {
+    // Guardians upload the wasm of a proposed upgrade in chunks, appended in call order. The
+    // attached deposit pays for the storage of the chunk, the rest is refunded.
+    // Returns the staged size.
+    #[payable]
+    pub fn stage_code(&mut self, code_hash: Base58CryptoHash, chunk: Base64VecU8) -> u32 {
+        self.assert_guardian();
+        if !self.is_code_hash_proposed(code_hash) {
+            panic!("No pending or passed upgrade proposes this code.");
+        }
+        let chunk_size = chunk.0.len() as u32;
+        if self.staged_code_size.saturating_add(chunk_size) > MAX_STAGED_CODE_SIZE {
+            panic!("Staged code can't exceed {} bytes in total.", MAX_STAGED_CODE_SIZE);
+        }
+
+        let guardian = env::predecessor_account_id();
+        let storage_usage = env::storage_usage();
+        let staging = self.staged_code.entry(code_hash).or_insert_with(|| CodeStaging {
+            code: Vec::new(),
+            storage_deposits: BTreeMap::new(),
+        });
+        if matches_hash(&staging.code, code_hash) {
+            panic!("Code is staged already.");
+        }
+        staging.code.extend(chunk.0);
+        let staged_size = staging.code.len() as u32;
+        self.staged_code_size += chunk_size;
+        // the map only writes its changes on flush, storage_usage would miss them otherwise
+        self.staged_code.flush();
+
+        let storage_cost = env::storage_byte_cost()
+            .saturating_mul(env::storage_usage().saturating_sub(storage_usage) as u128);
+        let attached_deposit = env::attached_deposit();
+        if attached_deposit < storage_cost {
+            panic!("Staging this chunk requires a storage deposit of {}.", storage_cost);
+        }
+        let staging = self.staged_code.get_mut(&code_hash).unwrap();
+        let deposit = staging.storage_deposits.entry(guardian.clone()).or_insert(NearToken::from_yoctonear(0));
+        *deposit = deposit.saturating_add(storage_cost);
+        let refund = attached_deposit.saturating_sub(storage_cost);
+        if !refund.is_zero() {
+            Promise::new(guardian).transfer(refund);
+        }
+        staged_size
+    }
+
+    // Drops partly or wrongly staged code to start over, and refunds its storage deposits.
+    pub fn clear_staged_code(&mut self, code_hash: Base58CryptoHash) -> bool {
+        self.assert_guardian();
+
+        if self.remove_staged_code(code_hash).is_none() {
+            panic!("Code is not staged");
+        }
+        true
+    }
+
+    pub fn get_staged_code(&self, code_hash: Base58CryptoHash) -> Option<StagedCode> {
+        self.staged_code.get(&code_hash).map(|staging| StagedCode {
+            size: staging.code.len() as u32,
+            complete: matches_hash(&staging.code, code_hash),
+        })
+    }
+
+    // Proposes deploying the code with the given hash. The code is staged once proposed,
+    // it is only needed once the proposal is executed.
+    #[payable]
+    pub fn propose_upgrade(&mut self, code_hash: Base58CryptoHash, migrate_method: Option<String>) -> String {
+        self.assert_not_paused(EPauseScope::SUBMISSIONS);
+        Contract::assert_proposal_deposit();
+        self.assert_guardian();
+
+        let mut proposal = ProposalInfo::without_project(EProposalKind::UPGRADE);
+        proposal.upgrade = Some(CodeUpgrade {
+            code_hash,
+            migrate_method: migrate_method.unwrap_or_else(|| DEFAULT_MIGRATE_METHOD.to_string()),
+        });
+
+        let proposal_id = self.next_proposal_id();
+        self.insert_proposal(proposal_id.clone(), proposal);
+        proposal_id
+    }
+}
@@ -0,0 +1,111 @@
+use crate::roles::ERole;
+use crate::{Contract, ContractExt, ProposalInfo};
+use near_sdk::store::LookupMap;
+use near_sdk::{near, AccountId};
+use std::collections::HashSet;
+
+// weight of a guardian without an explicit weight
+pub const DEFAULT_GUARDIAN_WEIGHT: u32 = 1;
+
+// Total weight of the guardians behind the votes and rejections of a proposal.
+#[near(serializers=[borsh, json])]
+#[derive(Default)]
+pub struct ProposalTally {
+    approval_weight: u32,
+    rejection_weight: u32,
+}
+
+impl ProposalTally {
+    pub(crate) fn approval_weight(&self) -> u32 {
+        self.approval_weight
+    }
+
+    pub(crate) fn rejection_weight(&self) -> u32 {
+        self.rejection_weight
+    }
+}
+
+fn total_weight(weights: &LookupMap<AccountId, u32>, accounts: &HashSet<AccountId>) -> u32 {
+    accounts
+        .iter()
+        .map(|account_id| *weights.get(account_id).unwrap_or(&DEFAULT_GUARDIAN_WEIGHT))
+        .sum()
+}
+
+// Takes the weights instead of the contract, so it can run while a proposal is borrowed mutably.
+pub(crate) fn tally(weights: &LookupMap<AccountId, u32>, proposal: &ProposalInfo) -> ProposalTally {
+    ProposalTally {
+        approval_weight: total_weight(weights, &proposal.votes),
+        rejection_weight: total_weight(weights, &proposal.rejections),
+    }
+}
+
+impl Contract {
+    pub(crate) fn total_guardian_weight(&self) -> u32 {
+        total_weight(&self.guardian_weights, &self.guardians)
+    }
+
+    pub(crate) fn heaviest_guardian_weight(&self) -> u32 {
+        self.guardians
+            .iter()
+            .map(|guardian| *self.guardian_weights.get(guardian).unwrap_or(&DEFAULT_GUARDIAN_WEIGHT))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Lowering a weight or revoking a guardian must not leave the threshold out of reach.
+    pub(crate) fn assert_vote_threshold_reachable(&self) {
+        let total_guardian_weight = self.total_guardian_weight();
+        if self.config.vote_threshold > total_guardian_weight {
+            panic!(
+                "Vote threshold {} exceeds the total guardian weight {}",
+                self.config.vote_threshold, total_guardian_weight
+            );
+        }
+    }
+
+    // Weights or votes changed outside of a vote, recount every pending proposal.
+    pub(crate) fn refresh_tallies(&mut self) {
+        for (_, proposal) in self.proposals.iter_mut() {
+            proposal.tally = tally(&self.guardian_weights, proposal);
+        }
+    }
+}
+
+#[near]
+impl Contract {
+    pub fn set_guardian_weight(&mut self, account_id: AccountId, weight: u32) {
+        self.assert_role(ERole::ADMIN);
+
+        if !self.guardians.contains(&account_id) {
+            panic!("{} is not a guardian", account_id);
+        }
+        // a single guardian must never reach the threshold alone
+        if weight == 0 || weight >= self.config.vote_threshold {
+            panic!("Guardian weight must be between 1 and {}", self.config.vote_threshold - 1);
+        }
+
+        self.guardian_weights.insert(account_id, weight);
+        self.assert_vote_threshold_reachable();
+        self.refresh_tallies();
+    }
+
+    pub fn get_guardian_weight(&self, account_id: AccountId) -> u32 {
+        *self
+            .guardian_weights
+            .get(&account_id)
+            .unwrap_or(&DEFAULT_GUARDIAN_WEIGHT)
+    }
+
+    // Weight of every current guardian, and their total.
+    pub fn list_guardian_weights(&self) -> (Vec<(&AccountId, u32)>, u32) {
+        let mut weights: Vec<(&AccountId, u32)> = self
+            .guardians
+            .iter()
+            .map(|guardian| (guardian, self.get_guardian_weight(guardian.clone())))
+            .collect();
+        weights.sort();
+        let total = weights.iter().map(|(_, weight)| weight).sum();
+        (weights, total)
+    }
+}
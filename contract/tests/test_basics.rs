@@ -1,6 +1,15 @@
+// the whitelist checks compare with true and false explicitly
+#![allow(clippy::bool_assert_comparison)]
+
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8};
+use near_sdk::CryptoHash;
+use near_whitelist::pagination::Page;
+use near_whitelist::ProjectInfo;
+use near_workspaces::network::Sandbox;
 use near_workspaces::types::NearToken;
+use near_workspaces::{Account, Contract, Worker};
 use serde_json::json;
-use near_whitelist::ProjectInfo;
+use std::collections::HashSet;
 
 #[tokio::test]
 async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>> {
@@ -127,6 +136,7 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         }))
         .transact()
         .await?;
+    use_shortest_execution_delay(&contract).await?;
 
     let _ = guardian_1
         .call(contract.id(), "vote_proposal")
@@ -150,6 +160,21 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         .transact()
         .await?;
 
+    // voting only passes the proposal, anyone can execute it once the execution delay is over
+    let initial_contract_outcome = contract
+        .view("check_contract_whitelisted")
+        .args_json(json!({"contract_id": "aa-harvest-moon.near"}))
+        .await?;
+    assert_eq!(initial_contract_outcome.json::<bool>()?, false);
+
+    wait_for_execution_delay(&sandbox, &contract, &proposal_id).await?;
+    let execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(execute_outcome.json::<bool>()?);
+
     let initial_contract_outcome = contract
         .view("check_contract_whitelisted")
         .args_json(json!({"contract_id": "aa-harvest-moon.near"}))
@@ -160,12 +185,24 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
     let rando_account_details_after_approving = rando_account.view_account().await?;
     assert!(rando_account_details_after_approving.balance > NearToken::from_near(99));
 
+    // the executed proposal is kept in the archive with its final votes
+    let archived_proposal = contract
+        .view("get_archived_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(archived_proposal["proposal"]["status"], "APPROVED");
+    assert_eq!(archived_proposal["proposal"]["votes"].as_array().unwrap().len(), 3);
+    assert_eq!(archived_proposal["finalized_by"], rando_account.id().to_string());
+
     let all_projects_outcome = contract
         .view("list_projects")
-        .args_json(json!({"from_index": 0, "limit": 20}))
+        .args_json(json!({"limit": 20}))
         .await?;
 
-    let all_projects = all_projects_outcome.json::<Vec<(String, ProjectInfo)>>()?;
+    let all_projects = all_projects_outcome
+        .json::<Page<String, (String, ProjectInfo)>>()?
+        .items;
 
     // update the project to remove aa-harvest-moon.near
     // and check the contract status again after proposal is approved
@@ -204,6 +241,12 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         }))
         .transact()
         .await?;
+    wait_for_execution_delay(&sandbox, &contract, &update_proposal_id).await?;
+    let _ = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": update_proposal_id}))
+        .transact()
+        .await?;
 
 
     let initial_contract_outcome = contract
@@ -212,5 +255,2166 @@ async fn test_contract_is_operational() -> Result<(), Box<dyn std::error::Error>
         .await?;
     assert_eq!(initial_contract_outcome.json::<bool>()?, false);
 
+    // both versions of the project are kept, each linked to its proposal
+    let project_versions = contract
+        .view("list_project_versions")
+        .args_json(json!({"project_id": all_projects[0].0}))
+        .await?
+        .json::<Page<u32, serde_json::Value>>()?;
+    assert_eq!(project_versions.total, Some(2));
+    assert_eq!(project_versions.items[0]["proposal_id"], proposal_id);
+    assert_eq!(project_versions.items[1]["proposal_id"], update_proposal_id);
+
+    // the removed contract keeps a closed whitelist period
+    let whitelist_periods = contract
+        .view("list_contract_whitelist_periods")
+        .args_json(json!({"contract_id": "aa-harvest-moon.near"}))
+        .await?
+        .json::<Vec<serde_json::Value>>()?;
+    assert_eq!(whitelist_periods.len(), 1);
+    assert!(!whitelist_periods[0]["until"].is_null());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_pagination() -> Result<(), Box<dyn std::error::Error>> {
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract_wasm = near_workspaces::compile_project("./").await?;
+
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    // past the ninth proposal the running id gets a second digit, ids are still listed newest first
+    let mut proposal_ids = Vec::new();
+    for index in 0..11 {
+        let add_project_outcome = rando_account
+            .call(contract.id(), "add_project")
+            .args_json(json!({
+                "contract_ids": [format!("contract-{}.near", index)],
+                "metadata": "{}",
+                "project_id": format!("project {}", index)
+            }))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?;
+        proposal_ids.push(add_project_outcome.json::<String>()?);
+    }
+    proposal_ids.reverse();
+
+    let first_page = contract
+        .view("list_proposals")
+        .args_json(json!({"limit": 6}))
+        .await?
+        .json::<Page<String, (String, serde_json::Value)>>()?;
+    assert_eq!(first_page.items.len(), 6);
+    assert_eq!(first_page.total, Some(11));
+    assert!(first_page.next_cursor.is_some());
+
+    let second_page = contract
+        .view("list_proposals")
+        .args_json(json!({"cursor": first_page.next_cursor, "limit": 6}))
+        .await?
+        .json::<Page<String, (String, serde_json::Value)>>()?;
+    assert_eq!(second_page.items.len(), 5);
+    assert!(second_page.next_cursor.is_none());
+    let listed_ids: Vec<String> = first_page
+        .items
+        .iter()
+        .chain(second_page.items.iter())
+        .map(|(proposal_id, _)| proposal_id.clone())
+        .collect();
+    assert_eq!(listed_ids, proposal_ids);
+
+    // limit is capped by the contract
+    let capped_page = contract
+        .view("list_proposals")
+        .args_json(json!({"limit": 1000}))
+        .await?
+        .json::<Page<String, (String, serde_json::Value)>>()?;
+    assert_eq!(capped_page.max_page_size, 50);
+    assert_eq!(capped_page.items.len(), 11);
+
+    Ok(())
+}
+
+// Shortest execution delay set_config accepts, 1 hour.
+const TEST_EXECUTION_DELAY: &str = "3600000000000";
+
+// Lowers the execution delay, so tests fast-forward one hour instead of a day per proposal.
+// The guardians must be added first, the threshold is checked against their weight.
+async fn use_shortest_execution_delay(contract: &Contract) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = contract.view("get_config").await?.json::<serde_json::Value>()?;
+    config["execution_delay"] = json!(TEST_EXECUTION_DELAY);
+    let set_config_outcome = contract
+        .call("set_config")
+        .args_json(json!({"config": config}))
+        .transact()
+        .await?;
+    assert!(set_config_outcome.is_success());
+    Ok(())
+}
+
+// Fast-forwards the sandbox until the passed proposal can be executed.
+async fn wait_for_execution_delay(
+    sandbox: &Worker<Sandbox>,
+    contract: &Contract,
+    proposal_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let executable_at = proposal["executable_at"]
+        .as_str()
+        .expect("Proposal has not passed")
+        .parse::<u64>()?;
+    while sandbox.view_block().await?.timestamp() < executable_at {
+        sandbox.fast_forward(1000).await?;
+    }
+    Ok(())
+}
+
+async fn approve_proposal(
+    sandbox: &Worker<Sandbox>,
+    guardians: &[Account],
+    contract: &Contract,
+    proposal_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for guardian in guardians {
+        let vote_outcome = guardian
+            .call(contract.id(), "vote_proposal")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .transact()
+            .await?;
+        assert!(vote_outcome.is_success());
+    }
+    wait_for_execution_delay(sandbox, contract, proposal_id).await?;
+
+    let execute_outcome = guardians[0]
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(execute_outcome.json::<bool>()?);
+    Ok(())
+}
+
+// Deploys the contract with three guardians of weight 1 and the shortest execution delay.
+async fn deploy_with_guardians() -> Result<(Worker<Sandbox>, Contract, Vec<Account>), Box<dyn std::error::Error>> {
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract_wasm = near_workspaces::compile_project("./").await?;
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+
+    let mut guardians = Vec::new();
+    for _ in 0..3 {
+        let guardian = sandbox.dev_create_account().await?;
+        let add_guardian_outcome = contract
+            .call("add_guardian")
+            .args_json(json!({"account_id": guardian.id().to_string()}))
+            .transact()
+            .await?;
+        assert!(add_guardian_outcome.is_success());
+        guardians.push(guardian);
+    }
+    use_shortest_execution_delay(&contract).await?;
+    Ok((sandbox, contract, guardians))
+}
+
+async fn add_approved_project(
+    sandbox: &Worker<Sandbox>,
+    guardians: &[Account],
+    contract: &Contract,
+    proposer: &Account,
+    project_id: &str,
+    contract_ids: &[&str],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let add_project_outcome = proposer
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": contract_ids,
+            "metadata": "{}",
+            "project_id": project_id
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(sandbox, guardians, contract, &add_project_outcome.json::<String>()?).await
+}
+
+#[tokio::test]
+async fn test_project_history_and_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near", "b.near"]).await?;
+    let first_timestamp = sandbox.view_block().await?.timestamp();
+    let update_outcome = rando_account
+        .call(contract.id(), "update_project")
+        .args_json(json!({
+            "contract_ids": ["b.near", "c.near"],
+            "metadata": "{\"description\":\"Updated\"}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &update_outcome.json::<String>()?).await?;
+    let second_timestamp = sandbox.view_block().await?.timestamp();
+
+    // every executed proposal adds a version
+    let versions = contract
+        .view("list_project_versions")
+        .args_json(json!({"project_id": "project"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(versions["total"], 2);
+    for (index, expected_contract_ids) in [(0, ["a.near", "b.near"]), (1, ["b.near", "c.near"])] {
+        let contract_ids: HashSet<String> = serde_json::from_value(versions["items"][index]["contract_ids"].clone())?;
+        assert_eq!(versions["items"][index]["version"], index + 1);
+        assert_eq!(contract_ids, HashSet::from(expected_contract_ids.map(String::from)));
+    }
+
+    let first_version = contract
+        .view("get_project_version_at")
+        .args_json(json!({"project_id": "project", "timestamp": first_timestamp.to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(first_version["version"], 1);
+
+    for (contract_id, timestamp, expected_whitelisted) in [
+        ("a.near", first_timestamp, true),
+        ("a.near", second_timestamp, false),
+        ("b.near", second_timestamp, true),
+        ("c.near", first_timestamp, false),
+        ("c.near", second_timestamp, true),
+    ] {
+        let was_whitelisted = contract
+            .view("was_contract_whitelisted_at")
+            .args_json(json!({"contract_id": contract_id, "timestamp": timestamp.to_string()}))
+            .await?
+            .json::<bool>()?;
+        assert_eq!(was_whitelisted, expected_whitelisted);
+    }
+
+    // pending proposals are indexed by proposer and by project until they are finalized
+    let update_outcome = rando_account
+        .call(contract.id(), "update_project")
+        .args_json(json!({
+            "contract_ids": ["c.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = update_outcome.json::<String>()?;
+    for (method, args) in [
+        ("list_proposals_by_proposer", json!({"account_id": rando_account.id().to_string()})),
+        ("list_proposals_by_project", json!({"project_id": "project"})),
+    ] {
+        let proposals = contract.view(method).args_json(args).await?.json::<serde_json::Value>()?;
+        assert_eq!(proposals["total"], 1);
+        assert_eq!(proposals["items"][0][0], proposal_id);
+    }
+
+    let cancel_outcome = rando_account
+        .call(contract.id(), "cancel_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(cancel_outcome.is_success());
+    let proposals = contract
+        .view("list_proposals_by_proposer")
+        .args_json(json!({"account_id": rando_account.id().to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposals["total"], 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stale_and_expired_proposals() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near"]).await?;
+
+    // the shortest voting period, so pending proposals can be expired
+    let mut config = contract.view("get_config").await?.json::<serde_json::Value>()?;
+    config["proposal_ttl"] = json!("86400000000000");
+    let set_config_outcome = contract
+        .call("set_config")
+        .args_json(json!({"config": config}))
+        .transact()
+        .await?;
+    assert!(set_config_outcome.is_success());
+
+    // three updates of the same project version: one executed, one pending and one passed
+    let mut proposal_ids = Vec::new();
+    for contract_ids in [["a.near", "b.near"], ["a.near", "c.near"], ["a.near", "d.near"]] {
+        let update_outcome = rando_account
+            .call(contract.id(), "update_project")
+            .args_json(json!({"project_id": "project", "contract_ids": contract_ids, "metadata": "{}"}))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?;
+        proposal_ids.push(update_outcome.json::<String>()?);
+    }
+    for proposal_id in [&proposal_ids[0], &proposal_ids[2]] {
+        for guardian in &guardians {
+            let vote_outcome = guardian
+                .call(contract.id(), "vote_proposal")
+                .args_json(json!({"proposal_id": proposal_id}))
+                .transact()
+                .await?;
+            assert!(vote_outcome.is_success());
+        }
+    }
+    let pending_proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let base_version = pending_proposal["base_versions"]["project"].clone();
+    assert!(!base_version.is_null());
+    assert_eq!(pending_proposal["stale"], false);
+
+    wait_for_execution_delay(&sandbox, &contract, &proposal_ids[0]).await?;
+    let execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[0]}))
+        .transact()
+        .await?;
+    assert!(execute_outcome.json::<bool>()?);
+
+    // the other proposals were based on the previous version of the project
+    for proposal_id in &proposal_ids[1..] {
+        let proposal = contract
+            .view("get_proposal_by_id")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .await?
+            .json::<serde_json::Value>()?;
+        assert_eq!(proposal["stale"], true);
+        assert_eq!(proposal["base_versions"]["project"], base_version);
+    }
+    let stale_vote_outcome = guardians[0]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .transact()
+        .await?;
+    assert!(stale_vote_outcome.is_failure());
+
+    // a stale passed proposal can't be executed, but is closed right away with its deposit refunded
+    let stale_execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[2]}))
+        .transact()
+        .await?;
+    assert!(stale_execute_outcome.is_failure());
+    let balance_before = rando_account.view_account().await?.balance;
+    let expire_outcome = guardians[0]
+        .call(contract.id(), "expire_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[2]}))
+        .transact()
+        .await?;
+    assert!(expire_outcome.is_success());
+    let balance_after = rando_account.view_account().await?.balance;
+    assert_eq!(balance_after, balance_before.saturating_add(NearToken::from_near(1)));
+
+    // a stale pending proposal is closed once its voting period is over
+    let early_expire_outcome = guardians[0]
+        .call(contract.id(), "expire_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .transact()
+        .await?;
+    assert!(early_expire_outcome.is_failure());
+    let created_at = pending_proposal["created_at"].as_str().unwrap().parse::<u64>()?;
+    while sandbox.view_block().await?.timestamp() < created_at + 86400000000000 {
+        sandbox.fast_forward(10000).await?;
+    }
+    let expire_outcome = guardians[0]
+        .call(contract.id(), "expire_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .transact()
+        .await?;
+    assert!(expire_outcome.is_success());
+
+    for proposal_id in &proposal_ids[1..] {
+        let archived_proposal = contract
+            .view("get_archived_proposal_by_id")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .await?
+            .json::<serde_json::Value>()?;
+        assert_eq!(archived_proposal["proposal"]["status"], "EXPIRED");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guardian_participation() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    let mut proposal_ids = Vec::new();
+    for (project_id, contract_id) in [("approved", "a.near"), ("pending", "b.near")] {
+        let add_project_outcome = rando_account
+            .call(contract.id(), "add_project")
+            .args_json(json!({
+                "contract_ids": [contract_id],
+                "metadata": "{}",
+                "project_id": project_id
+            }))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?;
+        proposal_ids.push(add_project_outcome.json::<String>()?);
+    }
+
+    let vote_outcome = guardians[0]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .transact()
+        .await?;
+    assert!(vote_outcome.is_success());
+    let withdraw_outcome = guardians[0]
+        .call(contract.id(), "withdraw_vote_on_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .transact()
+        .await?;
+    assert!(withdraw_outcome.is_success());
+    approve_proposal(&sandbox, &guardians, &contract, &proposal_ids[0]).await?;
+
+    // the withdrawn vote leaves the proposal awaiting the guardian
+    let awaiting_proposals = contract
+        .view("list_proposals_awaiting_vote")
+        .args_json(json!({"account_id": guardians[0].id().to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(awaiting_proposals["items"].as_array().unwrap().len(), 1);
+    assert_eq!(awaiting_proposals["items"][0][0], proposal_ids[1]);
+    assert!(awaiting_proposals["total"].is_null());
+
+    // votes are listed in the order they were cast
+    let votes = contract
+        .view("list_guardian_votes")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "limit": 2}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(votes["total"], 3);
+    assert_eq!(votes["next_cursor"], 2);
+    assert_eq!(votes["items"][0]["action"], "APPROVE");
+    assert_eq!(votes["items"][1]["action"], "WITHDRAW");
+    assert_eq!(votes["items"][1]["proposal_id"], proposal_ids[1]);
+    let votes = contract
+        .view("list_guardian_votes")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "cursor": 2}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(votes["items"][0]["proposal_id"], proposal_ids[0]);
+
+    let participation = contract
+        .view("get_guardian_participation")
+        .args_json(json!({"account_id": guardians[0].id().to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(participation["is_guardian"], true);
+    assert_eq!(participation["awaiting_votes"], 1);
+    assert_eq!(participation["stats"]["approvals"], 2);
+    assert_eq!(participation["stats"]["withdrawals"], 1);
+    assert_eq!(participation["stats"]["finalized_proposals"], 1);
+    assert_eq!(participation["stats"]["participated_proposals"], 1);
+
+    let participation = contract
+        .view("get_guardian_participation")
+        .args_json(json!({"account_id": rando_account.id().to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(participation["is_guardian"], false);
+    assert!(participation["stats"].is_null());
+
+    let participations = contract
+        .view("list_guardian_participation")
+        .await?
+        .json::<Vec<serde_json::Value>>()?;
+    assert_eq!(participations.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restructure_projects() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "source", &["a.near", "b.near"]).await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "target", &["c.near"]).await?;
+
+    // contracts can only be transferred out of the project owning them
+    let foreign_transfer_outcome = rando_account
+        .call(contract.id(), "transfer_contracts")
+        .args_json(json!({
+            "from_project_id": "source",
+            "to_project_id": "target",
+            "contract_ids": ["c.near"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(foreign_transfer_outcome.is_failure());
+
+    let transfer_outcome = rando_account
+        .call(contract.id(), "transfer_contracts")
+        .args_json(json!({
+            "from_project_id": "source",
+            "to_project_id": "target",
+            "contract_ids": ["a.near"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(transfer_outcome.is_success());
+    let transfer_proposal_id = transfer_outcome.json::<String>()?;
+
+    // voters see both projects as they will look after the transfer
+    let transfer_proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": transfer_proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(transfer_proposal["project_info"]["contract_ids"], json!(["b.near"]));
+    assert_eq!(transfer_proposal["related_project"]["contract_ids"].as_array().unwrap().len(), 2);
+
+    approve_proposal(&sandbox, &guardians, &contract, &transfer_proposal_id).await?;
+
+    let project_id = contract
+        .view("get_project_id_by_contract_id")
+        .args_json(json!({"contract_id": "a.near"}))
+        .await?
+        .json::<Option<String>>()?;
+    assert_eq!(project_id.as_deref(), Some("target"));
+
+    let merge_outcome = rando_account
+        .call(contract.id(), "merge_projects")
+        .args_json(json!({
+            "project_id": "source",
+            "into_project_id": "target",
+            "metadata": "{}"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(merge_outcome.is_success());
+    approve_proposal(&sandbox, &guardians, &contract, &merge_outcome.json::<String>()?).await?;
+
+    // the retired project id resolves to the project it was merged into
+    let merged_project = contract
+        .view("get_project_by_id")
+        .args_json(json!({"project_id": "source"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(merged_project["project_id"], "target");
+    assert_eq!(merged_project["contract_ids"].as_array().unwrap().len(), 3);
+
+    let rename_outcome = rando_account
+        .call(contract.id(), "rename_project")
+        .args_json(json!({"project_id": "target", "new_project_id": "renamed"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(rename_outcome.is_success());
+    approve_proposal(&sandbox, &guardians, &contract, &rename_outcome.json::<String>()?).await?;
+
+    // both old ids follow the rename
+    for project_id in ["source", "target"] {
+        let resolved_project_id = contract
+            .view("resolve_project_id")
+            .args_json(json!({"project_id": project_id}))
+            .await?
+            .json::<Option<String>>()?;
+        assert_eq!(resolved_project_id.as_deref(), Some("renamed"));
+    }
+    let project_id = contract
+        .view("get_project_id_by_contract_id")
+        .args_json(json!({"contract_id": "c.near"}))
+        .await?
+        .json::<Option<String>>()?;
+    assert_eq!(project_id.as_deref(), Some("renamed"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proposal_diffs_and_conflicts() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near", "b.near"]).await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "other", &["c.near"]).await?;
+
+    // the preview flags contracts owned by another project
+    let preview = contract
+        .view("preview_update_project")
+        .args_json(json!({
+            "project_id": "project",
+            "contract_ids": ["a.near", "c.near", "d.near"],
+            "metadata": "{\"description\":\"Updated\"}"
+        }))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(preview["contracts_added"], json!(["c.near", "d.near"]));
+    assert_eq!(preview["contracts_removed"], json!(["b.near"]));
+    assert_eq!(preview["contracts_conflicting"], json!([{"contract_id": "c.near", "project_id": "other"}]));
+    assert_eq!(preview["metadata_fields_changed"], json!(["description"]));
+
+    // and such an update is rejected when proposed
+    let conflicting_update_outcome = rando_account
+        .call(contract.id(), "update_project")
+        .args_json(json!({
+            "contract_ids": ["a.near", "c.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(conflicting_update_outcome.is_failure());
+
+    // a transfer changes both projects
+    let transfer_outcome = rando_account
+        .call(contract.id(), "transfer_contracts")
+        .args_json(json!({
+            "from_project_id": "other",
+            "to_project_id": "project",
+            "contract_ids": ["c.near"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let diffs = contract
+        .view("get_proposal_diff")
+        .args_json(json!({"proposal_id": transfer_outcome.json::<String>()?}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(diffs[0]["project_id"], "other");
+    assert_eq!(diffs[0]["contracts_removed"], json!(["c.near"]));
+    assert_eq!(diffs[1]["project_id"], "project");
+    assert_eq!(diffs[1]["contracts_added"], json!(["c.near"]));
+    assert_eq!(diffs[1]["contracts_conflicting"], json!([]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proposal_comments() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let other_account = sandbox.dev_create_account().await?;
+
+    let add_project_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["a.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = add_project_outcome.json::<String>()?;
+
+    // votes can carry their rationale
+    let reject_outcome = guardians[0]
+        .call(contract.id(), "reject_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": "Missing audit"}))
+        .transact()
+        .await?;
+    assert!(reject_outcome.is_success());
+    let comment_outcome = rando_account
+        .call(contract.id(), "comment_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": "Audit added"}))
+        .transact()
+        .await?;
+    assert!(comment_outcome.is_success());
+    let vote_outcome = guardians[0]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": "Fine now"}))
+        .transact()
+        .await?;
+    assert!(vote_outcome.is_success());
+
+    // only guardians and the proposer comment, within the length limit
+    let other_comment_outcome = other_account
+        .call(contract.id(), "comment_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": "Spam"}))
+        .transact()
+        .await?;
+    assert!(other_comment_outcome.is_failure());
+    let long_comment_outcome = guardians[1]
+        .call(contract.id(), "comment_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": "a".repeat(501)}))
+        .transact()
+        .await?;
+    assert!(long_comment_outcome.is_failure());
+    let empty_comment_outcome = guardians[1]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": ""}))
+        .transact()
+        .await?;
+    assert!(empty_comment_outcome.is_failure());
+
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    let comments = proposal["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 3);
+    assert_eq!(comments[0]["account_id"], guardians[0].id().to_string());
+    assert_eq!(comments[0]["action"], "REJECT");
+    assert_eq!(comments[0]["comment"], "Missing audit");
+    assert_eq!(comments[1]["account_id"], rando_account.id().to_string());
+    assert!(comments[1]["action"].is_null());
+    assert_eq!(comments[2]["action"], "APPROVE");
+    assert_eq!(proposal["votes"], json!([guardians[0].id().to_string()]));
+
+    // a proposal keeps at most 50 comments
+    for _ in comments.len()..50 {
+        let comment_outcome = rando_account
+            .call(contract.id(), "comment_proposal")
+            .args_json(json!({"proposal_id": proposal_id, "comment": "Bump"}))
+            .transact()
+            .await?;
+        assert!(comment_outcome.is_success());
+    }
+    let extra_comment_outcome = guardians[1]
+        .call(contract.id(), "comment_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "comment": "One more"}))
+        .transact()
+        .await?;
+    assert!(extra_comment_outcome.is_failure());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_roles_and_suspension() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let responder = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near"]).await?;
+
+    // guardians can't suspend projects
+    let guardian_suspend_outcome = guardians[0]
+        .call(contract.id(), "suspend_project")
+        .args_json(json!({"project_id": "project", "reason": "test"}))
+        .transact()
+        .await?;
+    assert!(guardian_suspend_outcome.is_failure());
+
+    let grant_outcome = contract
+        .call("grant_role")
+        .args_json(json!({"account_id": responder.id().to_string(), "role": "EMERGENCY"}))
+        .transact()
+        .await?;
+    assert!(grant_outcome.is_success());
+
+    let suspend_outcome = responder
+        .call(contract.id(), "suspend_project")
+        .args_json(json!({"project_id": "project", "reason": "test"}))
+        .transact()
+        .await?;
+    assert!(suspend_outcome.is_success());
+
+    let whitelisted = contract
+        .view("check_contract_whitelisted")
+        .args_json(json!({"contract_id": "a.near"}))
+        .await?
+        .json::<bool>()?;
+    assert!(!whitelisted);
+
+    // the responder can't lift the suspension, only an admin or the owner
+    let responder_lift_outcome = responder
+        .call(contract.id(), "lift_project_suspension")
+        .args_json(json!({"project_id": "project"}))
+        .transact()
+        .await?;
+    assert!(responder_lift_outcome.is_failure());
+
+    let lift_outcome = contract
+        .call("lift_project_suspension")
+        .args_json(json!({"project_id": "project"}))
+        .transact()
+        .await?;
+    assert!(lift_outcome.is_success());
+
+    let whitelisted = contract
+        .view("check_contract_whitelisted")
+        .args_json(json!({"contract_id": "a.near"}))
+        .await?
+        .json::<bool>()?;
+    assert!(whitelisted);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guardian_weights_and_delegation() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let hot_key = sandbox.dev_create_account().await?;
+
+    // weights are set by admins, for guardians only
+    let rando_weight_outcome = rando_account
+        .call(contract.id(), "set_guardian_weight")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "weight": 2}))
+        .transact()
+        .await?;
+    assert!(rando_weight_outcome.is_failure());
+    for (account_id, weight) in [(rando_account.id(), 2), (guardians[0].id(), 0)] {
+        let invalid_weight_outcome = contract
+            .call("set_guardian_weight")
+            .args_json(json!({"account_id": account_id.to_string(), "weight": weight}))
+            .transact()
+            .await?;
+        assert!(invalid_weight_outcome.is_failure());
+    }
+    let weight_outcome = contract
+        .call("set_guardian_weight")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "weight": 2}))
+        .transact()
+        .await?;
+    assert!(weight_outcome.is_success());
+    let (_, total_weight) = contract
+        .view("list_guardian_weights")
+        .await?
+        .json::<(Vec<(String, u32)>, u32)>()?;
+    assert_eq!(total_weight, 4);
+
+    // the threshold must stay reachable by the guardians and need more than one of them,
+    // and the execution delay must stay between 1 hour and 30 days
+    let config = contract.view("get_config").await?.json::<serde_json::Value>()?;
+    for (vote_threshold, execution_delay) in [
+        (1, TEST_EXECUTION_DELAY),
+        (5, TEST_EXECUTION_DELAY),
+        (3, "60000000000"),
+        (3, "2592000000000001"),
+    ] {
+        let mut invalid_config = config.clone();
+        invalid_config["vote_threshold"] = json!(vote_threshold);
+        invalid_config["execution_delay"] = json!(execution_delay);
+        let set_config_outcome = contract
+            .call("set_config")
+            .args_json(json!({"config": invalid_config}))
+            .transact()
+            .await?;
+        assert!(set_config_outcome.is_failure());
+    }
+    // a voting period of 0 would expire every proposal right away
+    let mut invalid_config = config.clone();
+    invalid_config["proposal_ttl"] = json!("0");
+    let set_config_outcome = contract
+        .call("set_config")
+        .args_json(json!({"config": invalid_config}))
+        .transact()
+        .await?;
+    assert!(set_config_outcome.is_failure());
+    let mut valid_config = config.clone();
+    valid_config["vote_threshold"] = json!(4);
+    let set_config_outcome = rando_account
+        .call(contract.id(), "set_config")
+        .args_json(json!({"config": valid_config}))
+        .transact()
+        .await?;
+    assert!(set_config_outcome.is_failure());
+
+    let add_project_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["a.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = add_project_outcome.json::<String>()?;
+
+    // a vote through a hot key counts with the weight of the guardian
+    let delegate_outcome = guardians[0]
+        .call(contract.id(), "delegate_vote")
+        .args_json(json!({"delegate": hot_key.id().to_string(), "duration": "604800000000000"}))
+        .transact()
+        .await?;
+    assert!(delegate_outcome.is_success());
+    let rando_delegate_outcome = rando_account
+        .call(contract.id(), "delegate_vote")
+        .args_json(json!({"delegate": hot_key.id().to_string(), "duration": "604800000000000"}))
+        .transact()
+        .await?;
+    assert!(rando_delegate_outcome.is_failure());
+
+    let vote_outcome = hot_key
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(vote_outcome.is_success());
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposal["tally"]["approval_weight"], 2);
+    assert_eq!(proposal["delegated_votes"][guardians[0].id().as_str()], hot_key.id().to_string());
+    assert_eq!(proposal["status"], "PENDING");
+
+    // the vote is credited to the guardian, not to the hot key
+    let guardian_votes = contract
+        .view("list_guardian_votes")
+        .args_json(json!({"account_id": guardians[0].id().to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(guardian_votes["items"][0]["proposal_id"], proposal_id);
+    assert_eq!(guardian_votes["items"][0]["action"], "APPROVE");
+    let participation = contract
+        .view("get_guardian_participation")
+        .args_json(json!({"account_id": hot_key.id().to_string()}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(participation["stats"].is_null());
+
+    // once revoked, the hot key can't vote anymore
+    let revoke_outcome = guardians[0]
+        .call(contract.id(), "revoke_vote_delegation")
+        .transact()
+        .await?;
+    assert!(revoke_outcome.is_success());
+    let revoked_vote_outcome = hot_key
+        .call(contract.id(), "reject_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(revoked_vote_outcome.is_failure());
+
+    let vote_outcome = guardians[1]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(vote_outcome.is_success());
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposal["tally"]["approval_weight"], 3);
+    assert_eq!(proposal["status"], "PASSED");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_heavy_guardian_cannot_pass_alone() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    // a weight reaching the threshold of 3 is refused
+    let heavy_weight_outcome = contract
+        .call("set_guardian_weight")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "weight": 3}))
+        .transact()
+        .await?;
+    assert!(heavy_weight_outcome.is_failure());
+    let weight_outcome = contract
+        .call("set_guardian_weight")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "weight": 2}))
+        .transact()
+        .await?;
+    assert!(weight_outcome.is_success());
+
+    // nor can the threshold be lowered to the weight of the heaviest guardian
+    let mut config = contract.view("get_config").await?.json::<serde_json::Value>()?;
+    config["vote_threshold"] = json!(2);
+    let set_config_outcome = contract
+        .call("set_config")
+        .args_json(json!({"config": config}))
+        .transact()
+        .await?;
+    assert!(set_config_outcome.is_failure());
+
+    let add_project_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["a.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = add_project_outcome.json::<String>()?;
+    let vote_outcome = guardians[0]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(vote_outcome.is_success());
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposal["tally"]["approval_weight"], 2);
+    assert_eq!(proposal["status"], "PENDING");
+
+    // the remaining guardians must keep the threshold reachable
+    let revoke_outcome = contract
+        .call("revoke_guardian")
+        .args_json(json!({"account_id": guardians[1].id().to_string()}))
+        .transact()
+        .await?;
+    assert!(revoke_outcome.is_success());
+    let unreachable_revoke_outcome = contract
+        .call("revoke_guardian")
+        .args_json(json!({"account_id": guardians[2].id().to_string()}))
+        .transact()
+        .await?;
+    assert!(unreachable_revoke_outcome.is_failure());
+    let unreachable_weight_outcome = contract
+        .call("set_guardian_weight")
+        .args_json(json!({"account_id": guardians[0].id().to_string(), "weight": 1}))
+        .transact()
+        .await?;
+    assert!(unreachable_weight_outcome.is_failure());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execution_delay_and_veto() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let emergency_account = sandbox.dev_create_account().await?;
+    let grant_outcome = contract
+        .call("grant_role")
+        .args_json(json!({"account_id": emergency_account.id().to_string(), "role": "EMERGENCY"}))
+        .transact()
+        .await?;
+    assert!(grant_outcome.is_success());
+
+    let mut proposal_ids = Vec::new();
+    for project_id in ["vetoed", "executed"] {
+        let add_project_outcome = rando_account
+            .call(contract.id(), "add_project")
+            .args_json(json!({
+                "contract_ids": [format!("{}.near", project_id)],
+                "metadata": "{}",
+                "project_id": project_id
+            }))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?;
+        let proposal_id = add_project_outcome.json::<String>()?;
+        for guardian in &guardians {
+            let vote_outcome = guardian
+                .call(contract.id(), "vote_proposal")
+                .args_json(json!({"proposal_id": proposal_id}))
+                .transact()
+                .await?;
+            assert!(vote_outcome.is_success());
+        }
+        proposal_ids.push(proposal_id);
+    }
+
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_ids[0]}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposal["status"], "PASSED");
+
+    // a passed proposal can't be executed during its delay
+    let early_execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[0]}))
+        .transact()
+        .await?;
+    assert!(early_execute_outcome.is_failure());
+
+    // only emergency responders veto
+    let rando_veto_outcome = rando_account
+        .call(contract.id(), "veto_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[0], "reason": "Spam"}))
+        .transact()
+        .await?;
+    assert!(rando_veto_outcome.is_failure());
+    let guardian_veto_outcome = guardians[0]
+        .call(contract.id(), "veto_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[0], "reason": "Spam"}))
+        .transact()
+        .await?;
+    assert!(guardian_veto_outcome.is_failure());
+
+    let veto_outcome = emergency_account
+        .call(contract.id(), "veto_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[0], "reason": "Spam"}))
+        .transact()
+        .await?;
+    assert!(veto_outcome.is_success());
+    let archived_proposal = contract
+        .view("get_archived_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_ids[0]}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(archived_proposal["proposal"]["status"], "VETOED");
+
+    // once the delay is over, the proposal can't be vetoed anymore and anyone can execute it
+    wait_for_execution_delay(&sandbox, &contract, &proposal_ids[1]).await?;
+    let late_veto_outcome = emergency_account
+        .call(contract.id(), "veto_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1], "reason": "Spam"}))
+        .transact()
+        .await?;
+    assert!(late_veto_outcome.is_failure());
+    let execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_ids[1]}))
+        .transact()
+        .await?;
+    assert!(execute_outcome.json::<bool>()?);
+
+    for (contract_id, expected_whitelisted) in [("vetoed.near", false), ("executed.near", true)] {
+        let whitelisted = contract
+            .view("check_contract_whitelisted")
+            .args_json(json!({"contract_id": contract_id}))
+            .await?
+            .json::<bool>()?;
+        assert_eq!(whitelisted, expected_whitelisted);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chunked_execution() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    // 250 contracts take three calls of up to 100 contracts each
+    let contract_ids: Vec<String> = (0..250).map(|index| format!("contract-{:03}.near", index)).collect();
+    let add_project_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": contract_ids,
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .max_gas()
+        .transact()
+        .await?;
+    let proposal_id = add_project_outcome.json::<String>()?;
+    let denylist_outcome = rando_account
+        .call(contract.id(), "propose_denylist")
+        .args_json(json!({
+            "contract_ids": ["scam.near"],
+            "reason": "DRAINER",
+            "evidence_urls": ["https://example.com/report"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let denylist_proposal_id = denylist_outcome.json::<String>()?;
+    for proposal_id in [&proposal_id, &denylist_proposal_id] {
+        for guardian in &guardians {
+            let vote_outcome = guardian
+                .call(contract.id(), "vote_proposal")
+                .args_json(json!({"proposal_id": proposal_id}))
+                .max_gas()
+                .transact()
+                .await?;
+            assert!(vote_outcome.is_success());
+        }
+    }
+    wait_for_execution_delay(&sandbox, &contract, &denylist_proposal_id).await?;
+
+    for expected_done in [100, 200] {
+        let execute_outcome = rando_account
+            .call(contract.id(), "execute_proposal")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(!execute_outcome.json::<bool>()?);
+
+        // contracts are moved in order, the project itself is only recorded at the end
+        let proposal = contract
+            .view("get_proposal_by_id")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .await?
+            .json::<serde_json::Value>()?;
+        assert_eq!(proposal["status"], "PASSED");
+        assert_eq!(proposal["execution"]["done"], expected_done);
+        let contracts = contract
+            .view("list_contracts")
+            .args_json(json!({"limit": 1}))
+            .await?
+            .json::<serde_json::Value>()?;
+        assert_eq!(contracts["total"], expected_done);
+        for (contract_id, expected_whitelisted) in [
+            (&contract_ids[expected_done - 1], true),
+            (&contract_ids[expected_done], false),
+        ] {
+            let whitelisted = contract
+                .view("check_contract_whitelisted")
+                .args_json(json!({"contract_id": contract_id}))
+                .await?
+                .json::<bool>()?;
+            assert_eq!(whitelisted, expected_whitelisted);
+        }
+        let project = contract
+            .view("get_project_by_id")
+            .args_json(json!({"project_id": "project"}))
+            .await?
+            .json::<Option<serde_json::Value>>()?;
+        assert!(project.is_none());
+
+        // no other proposal is executed in between, even one not touching projects
+        let denylist_execute_outcome = rando_account
+            .call(contract.id(), "execute_proposal")
+            .args_json(json!({"proposal_id": denylist_proposal_id}))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(denylist_execute_outcome.is_failure());
+    }
+
+    let execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(execute_outcome.json::<bool>()?);
+    let project = contract
+        .view("get_project_by_id")
+        .args_json(json!({"project_id": "project"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(project["contract_ids"].as_array().unwrap().len(), 250);
+
+    let denylist_execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": denylist_proposal_id}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(denylist_execute_outcome.json::<bool>()?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pause_scopes() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let emergency_account = sandbox.dev_create_account().await?;
+    let grant_outcome = contract
+        .call("grant_role")
+        .args_json(json!({"account_id": emergency_account.id().to_string(), "role": "EMERGENCY"}))
+        .transact()
+        .await?;
+    assert!(grant_outcome.is_success());
+
+    let add_project_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["a.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = add_project_outcome.json::<String>()?;
+
+    // only the owner and emergency responders pause
+    for account in [&rando_account, &guardians[0]] {
+        let pause_outcome = account
+            .call(contract.id(), "pause")
+            .args_json(json!({"scopes": ["SUBMISSIONS"], "reason": "Incident"}))
+            .transact()
+            .await?;
+        assert!(pause_outcome.is_failure());
+    }
+    let pause_outcome = emergency_account
+        .call(contract.id(), "pause")
+        .args_json(json!({"scopes": ["SUBMISSIONS", "VOTING", "EXECUTION"], "reason": "Incident"}))
+        .transact()
+        .await?;
+    assert!(pause_outcome.is_success());
+    let pause_status = contract.view("get_pause_status").await?.json::<serde_json::Value>()?;
+    assert_eq!(pause_status["voting"]["paused_by"], emergency_account.id().to_string());
+
+    let paused_submission_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["b.near"],
+            "metadata": "{}",
+            "project_id": "other"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(paused_submission_outcome.is_failure());
+    let paused_vote_outcome = guardians[0]
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(paused_vote_outcome.is_failure());
+
+    // guardians unpause through a proposal, which is exempt from the pause
+    let rando_unpause_outcome = rando_account
+        .call(contract.id(), "propose_unpause")
+        .args_json(json!({"scopes": ["VOTING"]}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(rando_unpause_outcome.is_failure());
+    let unpause_outcome = guardians[0]
+        .call(contract.id(), "propose_unpause")
+        .args_json(json!({"scopes": ["SUBMISSIONS", "VOTING"]}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &unpause_outcome.json::<String>()?).await?;
+    let pause_status = contract.view("get_pause_status").await?.json::<serde_json::Value>()?;
+    assert!(pause_status["submissions"].is_null());
+    assert!(pause_status["voting"].is_null());
+    assert!(!pause_status["execution"].is_null());
+
+    // votes go through again, but the passed proposal waits for execution to be unpaused
+    for guardian in &guardians {
+        let vote_outcome = guardian
+            .call(contract.id(), "vote_proposal")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .transact()
+            .await?;
+        assert!(vote_outcome.is_success());
+    }
+    wait_for_execution_delay(&sandbox, &contract, &proposal_id).await?;
+    let paused_execute_outcome = rando_account
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(paused_execute_outcome.is_failure());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_staged_code_upgrade() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    // the same code is deployed again, so there is no state to migrate
+    let code = near_workspaces::compile_project("./").await?;
+    let code_hash = Base58CryptoHash::from(near_sdk::env::sha256_array(&code));
+    let (first_chunk, last_chunk) = code.split_at(code.len() / 2);
+
+    // only guardians stage code and propose upgrades
+    let rando_stage_outcome = rando_account
+        .call(contract.id(), "stage_code")
+        .args_json(json!({"code_hash": code_hash, "chunk": Base64VecU8::from(first_chunk.to_vec())}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(rando_stage_outcome.is_failure());
+    let rando_propose_outcome = rando_account
+        .call(contract.id(), "propose_upgrade")
+        .args_json(json!({"code_hash": code_hash}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(rando_propose_outcome.is_failure());
+
+    // only proposed code is staged, and its storage is paid by the guardian staging it
+    let unproposed_stage_outcome = guardians[0]
+        .call(contract.id(), "stage_code")
+        .args_json(json!({"code_hash": code_hash, "chunk": Base64VecU8::from(first_chunk.to_vec())}))
+        .deposit(NearToken::from_near(10))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(unproposed_stage_outcome.is_failure());
+
+    let propose_outcome = guardians[0]
+        .call(contract.id(), "propose_upgrade")
+        .args_json(json!({"code_hash": code_hash, "migrate_method": "get_config"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = propose_outcome.json::<String>()?;
+
+    let no_deposit_stage_outcome = guardians[0]
+        .call(contract.id(), "stage_code")
+        .args_json(json!({"code_hash": code_hash, "chunk": Base64VecU8::from(first_chunk.to_vec())}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(no_deposit_stage_outcome.is_failure());
+    let stage_outcome = guardians[0]
+        .call(contract.id(), "stage_code")
+        .args_json(json!({"code_hash": code_hash, "chunk": Base64VecU8::from(first_chunk.to_vec())}))
+        .deposit(NearToken::from_near(10))
+        .max_gas()
+        .transact()
+        .await?;
+    assert_eq!(stage_outcome.json::<u32>()?, first_chunk.len() as u32);
+    for guardian in &guardians {
+        let vote_outcome = guardian
+            .call(contract.id(), "vote_proposal")
+            .args_json(json!({"proposal_id": proposal_id}))
+            .transact()
+            .await?;
+        assert!(vote_outcome.is_success());
+    }
+    wait_for_execution_delay(&sandbox, &contract, &proposal_id).await?;
+
+    // partly staged code is never deployed
+    let early_execute_outcome = guardians[0]
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(early_execute_outcome.is_failure());
+
+    let stage_outcome = guardians[1]
+        .call(contract.id(), "stage_code")
+        .args_json(json!({"code_hash": code_hash, "chunk": Base64VecU8::from(last_chunk.to_vec())}))
+        .deposit(NearToken::from_near(10))
+        .max_gas()
+        .transact()
+        .await?;
+    assert_eq!(stage_outcome.json::<u32>()?, code.len() as u32);
+    let staged_code = contract
+        .view("get_staged_code")
+        .args_json(json!({"code_hash": code_hash}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(staged_code["complete"], true);
+
+    let execute_outcome = guardians[0]
+        .call(contract.id(), "execute_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(execute_outcome.is_success());
+    assert!(execute_outcome.receipt_failures().is_empty());
+
+    // the staged code is consumed by the upgrade, and the state is kept
+    let staged_code = contract
+        .view("get_staged_code")
+        .args_json(json!({"code_hash": code_hash}))
+        .await?
+        .json::<Option<serde_json::Value>>()?;
+    assert!(staged_code.is_none());
+    let archived_proposal = contract
+        .view("get_archived_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(archived_proposal["proposal"]["status"], "APPROVED");
+
+    // the code staged for a rejected upgrade is deleted
+    let rejected_code = b"rejected upgrade";
+    let rejected_code_hash = Base58CryptoHash::from(near_sdk::env::sha256_array(rejected_code));
+    let propose_outcome = guardians[0]
+        .call(contract.id(), "propose_upgrade")
+        .args_json(json!({"code_hash": rejected_code_hash}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let rejected_proposal_id = propose_outcome.json::<String>()?;
+    let stage_outcome = guardians[0]
+        .call(contract.id(), "stage_code")
+        .args_json(json!({"code_hash": rejected_code_hash, "chunk": Base64VecU8::from(rejected_code.to_vec())}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(stage_outcome.is_success());
+    for guardian in &guardians {
+        let reject_outcome = guardian
+            .call(contract.id(), "reject_proposal")
+            .args_json(json!({"proposal_id": rejected_proposal_id}))
+            .transact()
+            .await?;
+        assert!(reject_outcome.is_success());
+    }
+    let staged_code = contract
+        .view("get_staged_code")
+        .args_json(json!({"code_hash": rejected_code_hash}))
+        .await?
+        .json::<Option<serde_json::Value>>()?;
+    assert!(staged_code.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ownership_transfer_and_renounce() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, _guardians) = deploy_with_guardians().await?;
+    let new_owner = sandbox.dev_create_account().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    // only the owner, the contract account by default, proposes a new owner
+    let rando_propose_outcome = rando_account
+        .call(contract.id(), "propose_owner")
+        .args_json(json!({"account_id": rando_account.id().to_string()}))
+        .transact()
+        .await?;
+    assert!(rando_propose_outcome.is_failure());
+
+    let propose_outcome = contract
+        .call("propose_owner")
+        .args_json(json!({"account_id": new_owner.id().to_string()}))
+        .transact()
+        .await?;
+    assert!(propose_outcome.is_success());
+    let pending_owner = contract.view("get_pending_owner").await?.json::<Option<String>>()?;
+    assert_eq!(pending_owner, Some(new_owner.id().to_string()));
+
+    // only the proposed owner can accept
+    let rando_accept_outcome = rando_account.call(contract.id(), "accept_ownership").transact().await?;
+    assert!(rando_accept_outcome.is_failure());
+    let accept_outcome = new_owner.call(contract.id(), "accept_ownership").transact().await?;
+    assert!(accept_outcome.is_success());
+    let owner = contract.view("get_owner").await?.json::<Option<String>>()?;
+    assert_eq!(owner, Some(new_owner.id().to_string()));
+    let pending_owner = contract.view("get_pending_owner").await?.json::<Option<String>>()?;
+    assert_eq!(pending_owner, None);
+
+    // the previous owner lost its rights
+    let old_owner_grant_outcome = contract
+        .call("grant_role")
+        .args_json(json!({"account_id": rando_account.id().to_string(), "role": "ADMIN"}))
+        .transact()
+        .await?;
+    assert!(old_owner_grant_outcome.is_failure());
+
+    let grant_outcome = new_owner
+        .call(contract.id(), "grant_role")
+        .args_json(json!({"account_id": rando_account.id().to_string(), "role": "CURATOR"}))
+        .transact()
+        .await?;
+    assert!(grant_outcome.is_success());
+
+    // once renounced, roles can't be granted or revoked anymore
+    let rando_renounce_outcome = rando_account.call(contract.id(), "renounce_ownership").transact().await?;
+    assert!(rando_renounce_outcome.is_failure());
+    let renounce_outcome = new_owner.call(contract.id(), "renounce_ownership").transact().await?;
+    assert!(renounce_outcome.is_success());
+    let owner = contract.view("get_owner").await?.json::<Option<String>>()?;
+    assert_eq!(owner, None);
+
+    let grant_outcome = new_owner
+        .call(contract.id(), "grant_role")
+        .args_json(json!({"account_id": rando_account.id().to_string(), "role": "ADMIN"}))
+        .transact()
+        .await?;
+    assert!(grant_outcome.is_failure());
+    let revoke_outcome = new_owner
+        .call(contract.id(), "revoke_role")
+        .args_json(json!({"account_id": rando_account.id().to_string(), "role": "CURATOR"}))
+        .transact()
+        .await?;
+    assert!(revoke_outcome.is_failure());
+    let is_curator = contract
+        .view("has_role")
+        .args_json(json!({"account_id": rando_account.id().to_string(), "role": "CURATOR"}))
+        .await?
+        .json::<bool>()?;
+    assert!(is_curator);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_denylist_verdict() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near"]).await?;
+
+    // evidence is required
+    let no_evidence_outcome = rando_account
+        .call(contract.id(), "propose_denylist")
+        .args_json(json!({"contract_ids": ["scam.near"], "reason": "DRAINER", "evidence_urls": []}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(no_evidence_outcome.is_failure());
+
+    let denylist_outcome = rando_account
+        .call(contract.id(), "propose_denylist")
+        .args_json(json!({
+            "contract_ids": ["a.near", "scam.near"],
+            "reason": "DRAINER",
+            "evidence_urls": ["https://example.com/report"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &denylist_outcome.json::<String>()?).await?;
+
+    for (contract_id, expected_verdict) in [("a.near", "MALICIOUS"), ("scam.near", "MALICIOUS"), ("b.near", "UNKNOWN")] {
+        let verdict = contract
+            .view("get_contract_verdict")
+            .args_json(json!({"contract_id": contract_id}))
+            .await?
+            .json::<String>()?;
+        assert_eq!(verdict, expected_verdict);
+    }
+
+    // a denylisted contract is not whitelisted, even when its project is approved
+    let whitelisted = contract
+        .view("check_contract_whitelisted")
+        .args_json(json!({"contract_id": "a.near"}))
+        .await?
+        .json::<bool>()?;
+    assert!(!whitelisted);
+
+    let undenylist_outcome = rando_account
+        .call(contract.id(), "propose_undenylist")
+        .args_json(json!({"contract_ids": ["a.near"]}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &undenylist_outcome.json::<String>()?).await?;
+
+    let verdict = contract
+        .view("get_contract_verdict")
+        .args_json(json!({"contract_id": "a.near"}))
+        .await?
+        .json::<String>()?;
+    assert_eq!(verdict, "TRUSTED");
+
+    // the same verdict is returned to other contracts by query_verdict
+    let query_outcome = rando_account
+        .call(contract.id(), "query_verdict")
+        .args_json(json!({"account_id": "a.near"}))
+        .transact()
+        .await?;
+    let response = query_outcome.json::<serde_json::Value>()?;
+    assert_eq!(response["verdict"], "TRUSTED");
+    assert_eq!(response["project_id"], "project");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_verdict_through_ext_contract() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near"]).await?;
+
+    // the example contract calls query_verdict through ext_whitelist and reads it in a callback
+    let gate_wasm = near_workspaces::compile_project("./examples/verdict-gate").await?;
+    let gate = sandbox.dev_deploy(&gate_wasm).await?;
+    let init_outcome = gate
+        .call("new")
+        .args_json(json!({"whitelist_id": contract.id()}))
+        .transact()
+        .await?;
+    assert!(init_outcome.is_success());
+
+    let trusted_outcome = rando_account
+        .call(gate.id(), "check_account")
+        .args_json(json!({"account_id": "a.near"}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(trusted_outcome.is_success());
+    assert!(trusted_outcome.json::<bool>()?);
+    let last_verdict = gate.view("get_last_verdict").await?.json::<Option<String>>()?;
+    assert_eq!(last_verdict.as_deref(), Some("TRUSTED"));
+
+    let unknown_outcome = rando_account
+        .call(gate.id(), "check_account")
+        .args_json(json!({"account_id": "b.near"}))
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(unknown_outcome.is_success());
+    assert!(!unknown_outcome.json::<bool>()?);
+    let last_verdict = gate.view("get_last_verdict").await?.json::<Option<String>>()?;
+    assert_eq!(last_verdict.as_deref(), Some("UNKNOWN"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_report_bonds_and_escalation() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near"]).await?;
+
+    // a report needs the bond and a whitelisted target
+    let no_bond_outcome = rando_account
+        .call(contract.id(), "report")
+        .args_json(json!({
+            "contract_id": "a.near",
+            "description": "Drains approvals",
+            "evidence_urls": ["https://example.com/report"]
+        }))
+        .transact()
+        .await?;
+    assert!(no_bond_outcome.is_failure());
+    let unknown_contract_outcome = rando_account
+        .call(contract.id(), "report")
+        .args_json(json!({
+            "contract_id": "b.near",
+            "description": "Drains approvals",
+            "evidence_urls": ["https://example.com/report"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(unknown_contract_outcome.is_failure());
+
+    let mut reporters = Vec::new();
+    let mut report_ids = Vec::new();
+    for _ in 0..3 {
+        let reporter = sandbox.dev_create_account().await?;
+        let report_outcome = reporter
+            .call(contract.id(), "report")
+            .args_json(json!({
+                "contract_id": "a.near",
+                "description": "Drains approvals",
+                "evidence_urls": ["https://example.com/report"]
+            }))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?;
+        report_ids.push(report_outcome.json::<u32>()?);
+        reporters.push(reporter);
+    }
+    let queue = contract.view("list_report_queue").await?.json::<serde_json::Value>()?;
+    assert_eq!(queue["total"], 3);
+
+    // only guardians resolve, and only accepted reports are escalated
+    let rando_resolve_outcome = rando_account
+        .call(contract.id(), "resolve_report")
+        .args_json(json!({"report_id": report_ids[0], "status": "DISMISSED", "note": "Unfounded"}))
+        .transact()
+        .await?;
+    assert!(rando_resolve_outcome.is_failure());
+    let dismissed_escalation_outcome = guardians[0]
+        .call(contract.id(), "resolve_report")
+        .args_json(json!({
+            "report_id": report_ids[0],
+            "status": "DISMISSED",
+            "note": "Unfounded",
+            "escalation": "REMOVAL"
+        }))
+        .transact()
+        .await?;
+    assert!(dismissed_escalation_outcome.is_failure());
+
+    // a dismissed report gets its bond back, a frivolous one does not
+    for (index, status, expected_refund) in [
+        (0, "DISMISSED", NearToken::from_near(1)),
+        (1, "FRIVOLOUS", NearToken::from_near(0)),
+    ] {
+        let balance_before = reporters[index].view_account().await?.balance;
+        let resolve_outcome = guardians[0]
+            .call(contract.id(), "resolve_report")
+            .args_json(json!({"report_id": report_ids[index], "status": status, "note": "Reviewed"}))
+            .transact()
+            .await?;
+        assert!(resolve_outcome.is_success());
+        let balance_after = reporters[index].view_account().await?.balance;
+        assert_eq!(balance_after, balance_before.saturating_add(expected_refund));
+    }
+
+    // a report is escalated against the project under its current id
+    let rename_outcome = rando_account
+        .call(contract.id(), "rename_project")
+        .args_json(json!({"project_id": "project", "new_project_id": "renamed"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &rename_outcome.json::<String>()?).await?;
+    let escalate_outcome = guardians[0]
+        .call(contract.id(), "resolve_report")
+        .args_json(json!({
+            "report_id": report_ids[2],
+            "status": "ACCEPTED",
+            "note": "Confirmed",
+            "escalation": "REMOVAL"
+        }))
+        .transact()
+        .await?;
+    assert!(escalate_outcome.is_success());
+
+    let report = contract
+        .view("get_report")
+        .args_json(json!({"report_id": report_ids[2]}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(report["status"], "ACCEPTED");
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": report["resolution"]["proposal_id"]}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposal["kind"], "REMOVE");
+    assert_eq!(proposal["project_id"], "renamed");
+    assert_eq!(proposal["proposed_by"], reporters[2].id().to_string());
+
+    let queue = contract.view("list_report_queue").await?.json::<serde_json::Value>()?;
+    assert_eq!(queue["total"], 0);
+
+    Ok(())
+}
+
+async fn screen_transaction(
+    contract: &Contract,
+    receiver_id: &str,
+    method_name: &str,
+    attached_deposit: &str,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let screening = contract
+        .view("screen_transaction")
+        .args_json(json!({
+            "receiver_id": receiver_id,
+            "method_name": method_name,
+            "attached_deposit": attached_deposit
+        }))
+        .await?
+        .json::<serde_json::Value>()?;
+    Ok(screening)
+}
+
+#[tokio::test]
+async fn test_screen_transaction() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    for (project_id, contract_id, audit_expires_at) in
+        [("audited", "a.near", "9000000000000000000"), ("expired", "b.near", "1000000000000000000")]
+    {
+        let metadata = json!({"audit_report_url": "https://example.com/audit", "audit_expires_at": audit_expires_at});
+        let add_project_outcome = rando_account
+            .call(contract.id(), "add_project")
+            .args_json(json!({
+                "contract_ids": [contract_id],
+                "metadata": metadata.to_string(),
+                "project_id": project_id
+            }))
+            .deposit(NearToken::from_near(1))
+            .transact()
+            .await?;
+        approve_proposal(&sandbox, &guardians, &contract, &add_project_outcome.json::<String>()?).await?;
+    }
+
+    let screening = screen_transaction(&contract, "a.near", "ft_transfer", "1").await?;
+    assert_eq!(screening["verdict"], "TRUSTED");
+    assert_eq!(screening["project_id"], "audited");
+    assert_eq!(screening["tier"], "AUDITED");
+    assert_eq!(screening["warnings"], json!([]));
+
+    let screening = screen_transaction(&contract, "b.near", "nft_approve", "0").await?;
+    assert_eq!(screening["verdict"], "TRUSTED");
+    assert_eq!(screening["tier"], "LISTED");
+    assert_eq!(screening["warnings"], json!(["AUDIT_EXPIRED", "TOKEN_APPROVAL"]));
+
+    let screening = screen_transaction(&contract, "z.near", "ft_transfer", "1").await?;
+    assert_eq!(screening["verdict"], "UNKNOWN");
+    assert!(screening["tier"].is_null());
+    assert_eq!(screening["warnings"], json!(["DEPOSIT_TO_UNTRUSTED"]));
+
+    // open reports follow the project when it is renamed
+    let report_outcome = rando_account
+        .call(contract.id(), "report")
+        .args_json(json!({
+            "contract_id": "b.near",
+            "description": "Drains approvals",
+            "evidence_urls": ["https://example.com/report"]
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let report_id = report_outcome.json::<u32>()?;
+    let rename_outcome = rando_account
+        .call(contract.id(), "rename_project")
+        .args_json(json!({"project_id": "expired", "new_project_id": "renamed"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &rename_outcome.json::<String>()?).await?;
+
+    let screening = screen_transaction(&contract, "b.near", "ft_transfer", "0").await?;
+    assert_eq!(screening["project_id"], "renamed");
+    assert_eq!(screening["warnings"], json!(["AUDIT_EXPIRED", "OPEN_REPORTS"]));
+
+    let resolve_outcome = guardians[0]
+        .call(contract.id(), "resolve_report")
+        .args_json(json!({"report_id": report_id, "status": "DISMISSED", "note": "Unfounded"}))
+        .transact()
+        .await?;
+    assert!(resolve_outcome.is_success());
+    let screening = screen_transaction(&contract, "b.near", "ft_transfer", "0").await?;
+    assert_eq!(screening["warnings"], json!(["AUDIT_EXPIRED"]));
+
+    // a pending removal is flagged, and a suspension changes the verdict
+    let remove_outcome = rando_account
+        .call(contract.id(), "remove_project")
+        .args_json(json!({"project_id": "renamed"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(remove_outcome.is_success());
+    let suspend_outcome = contract
+        .call("suspend_project")
+        .args_json(json!({"project_id": "renamed", "reason": "Incident"}))
+        .transact()
+        .await?;
+    assert!(suspend_outcome.is_success());
+
+    let screening = screen_transaction(&contract, "b.near", "ft_transfer", "1").await?;
+    assert_eq!(screening["verdict"], "SUSPENDED");
+    assert_eq!(
+        screening["warnings"],
+        json!(["AUDIT_EXPIRED", "PENDING_REMOVAL", "DEPOSIT_TO_UNTRUSTED"])
+    );
+
+    Ok(())
+}
+
+fn merkle_hash(tag: u8, left: &CryptoHash, right: &CryptoHash) -> CryptoHash {
+    let mut bytes = vec![tag];
+    bytes.extend_from_slice(left);
+    bytes.extend_from_slice(right);
+    near_sdk::env::sha256_array(&bytes)
+}
+
+fn proof_hash(value: &serde_json::Value) -> CryptoHash {
+    serde_json::from_value::<Base58CryptoHash>(value.clone()).unwrap().into()
+}
+
+// Recomputes the root from a proof returned by get_whitelist_proof, the way a client verifies it.
+fn whitelist_proof_root(proof: &serde_json::Value) -> CryptoHash {
+    let key = proof_hash(&proof["key"]);
+    let mut hash = if let Some(project_id) = proof["project_id"].as_str() {
+        merkle_hash(0, &key, &near_sdk::env::sha256_array(project_id.as_bytes()))
+    } else if !proof["other_leaf"].is_null() {
+        merkle_hash(
+            0,
+            &proof_hash(&proof["other_leaf"]["key"]),
+            &proof_hash(&proof["other_leaf"]["value_hash"]),
+        )
+    } else {
+        [0; 32]
+    };
+
+    let siblings = proof["siblings"].as_array().unwrap();
+    for (depth, sibling) in siblings.iter().enumerate().rev() {
+        let sibling = proof_hash(sibling);
+        let is_right = (key[depth / 8] >> (7 - depth % 8)) & 1 == 1;
+        hash = if is_right {
+            merkle_hash(1, &sibling, &hash)
+        } else {
+            merkle_hash(1, &hash, &sibling)
+        };
+    }
+    hash
+}
+
+#[tokio::test]
+async fn test_whitelist_proofs() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+
+    let empty_root = contract.view("get_whitelist_root").await?.json::<Base58CryptoHash>()?;
+    assert_eq!(CryptoHash::from(empty_root), [0; 32]);
+
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near", "b.near", "c.near"]).await?;
+    let root = CryptoHash::from(contract.view("get_whitelist_root").await?.json::<Base58CryptoHash>()?);
+    assert_ne!(root, [0; 32]);
+
+    // inclusion proofs
+    for contract_id in ["a.near", "b.near", "c.near"] {
+        let proof = contract
+            .view("get_whitelist_proof")
+            .args_json(json!({"contract_id": contract_id}))
+            .await?
+            .json::<serde_json::Value>()?;
+        assert_eq!(proof_hash(&proof["key"]), near_sdk::env::sha256_array(contract_id.as_bytes()));
+        assert_eq!(proof["project_id"], "project");
+        assert_eq!(proof_hash(&proof["root"]), root);
+        assert_eq!(whitelist_proof_root(&proof), root);
+    }
+
+    // non-inclusion proof
+    let proof = contract
+        .view("get_whitelist_proof")
+        .args_json(json!({"contract_id": "z.near"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(proof["project_id"].is_null());
+    assert_eq!(whitelist_proof_root(&proof), root);
+
+    // a tampered proof does not match the root
+    let mut proof = contract
+        .view("get_whitelist_proof")
+        .args_json(json!({"contract_id": "a.near"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    proof["project_id"] = json!("other-project");
+    assert_ne!(whitelist_proof_root(&proof), root);
+    proof["project_id"] = json!("project");
+    proof["siblings"][0] = json!(Base58CryptoHash::from([1; 32]));
+    assert_ne!(whitelist_proof_root(&proof), root);
+
+    // the root follows removals
+    let update_outcome = rando_account
+        .call(contract.id(), "update_project")
+        .args_json(json!({
+            "contract_ids": ["a.near", "b.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &update_outcome.json::<String>()?).await?;
+    let updated_root = CryptoHash::from(contract.view("get_whitelist_root").await?.json::<Base58CryptoHash>()?);
+    assert_ne!(updated_root, root);
+    let proof = contract
+        .view("get_whitelist_proof")
+        .args_json(json!({"contract_id": "c.near"}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert!(proof["project_id"].is_null());
+    assert_eq!(whitelist_proof_root(&proof), updated_root);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_amend_proposal_resets_votes() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let hot_key = sandbox.dev_create_account().await?;
+
+    let add_project_outcome = rando_account
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["a.near"],
+            "metadata": "{}",
+            "project_id": "project"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    let proposal_id = add_project_outcome.json::<String>()?;
+
+    // the first guardian votes through a hot key
+    let delegate_outcome = guardians[0]
+        .call(contract.id(), "delegate_vote")
+        .args_json(json!({"delegate": hot_key.id().to_string(), "duration": "604800000000000"}))
+        .transact()
+        .await?;
+    assert!(delegate_outcome.is_success());
+    let vote_outcome = hot_key
+        .call(contract.id(), "vote_proposal")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .transact()
+        .await?;
+    assert!(vote_outcome.is_success());
+
+    let amend_outcome = rando_account
+        .call(contract.id(), "amend_proposal")
+        .args_json(json!({"proposal_id": proposal_id, "metadata": "{\"description\":\"Amended\"}"}))
+        .transact()
+        .await?;
+    assert!(amend_outcome.is_success());
+
+    // the vote moves to the amendment history, along with its delegate attribution
+    let proposal = contract
+        .view("get_proposal_by_id")
+        .args_json(json!({"proposal_id": proposal_id}))
+        .await?
+        .json::<serde_json::Value>()?;
+    assert_eq!(proposal["votes"], json!([]));
+    assert_eq!(proposal["delegated_votes"], json!({}));
+    assert_eq!(proposal["tally"]["approval_weight"], 0);
+    assert_eq!(proposal["amendments"][0]["votes"], json!([guardians[0].id().to_string()]));
+
+    // the amendment history is bounded, to 10 amendments
+    for amendment in 2..=11 {
+        let amend_outcome = rando_account
+            .call(contract.id(), "amend_proposal")
+            .args_json(json!({
+                "proposal_id": proposal_id,
+                "metadata": json!({"description": format!("Amendment {}", amendment)}).to_string()
+            }))
+            .transact()
+            .await?;
+        assert_eq!(amend_outcome.is_success(), amendment <= 10);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_removed_project_id_stays_reserved() -> Result<(), Box<dyn std::error::Error>> {
+    let (sandbox, contract, guardians) = deploy_with_guardians().await?;
+    let rando_account = sandbox.dev_create_account().await?;
+    let attacker = sandbox.dev_create_account().await?;
+    add_approved_project(&sandbox, &guardians, &contract, &rando_account, "project", &["a.near"]).await?;
+
+    let rename_outcome = rando_account
+        .call(contract.id(), "rename_project")
+        .args_json(json!({"project_id": "project", "new_project_id": "renamed"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &rename_outcome.json::<String>()?).await?;
+
+    let remove_outcome = rando_account
+        .call(contract.id(), "remove_project")
+        .args_json(json!({"project_id": "renamed"}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    approve_proposal(&sandbox, &guardians, &contract, &remove_outcome.json::<String>()?).await?;
+
+    // the old id resolves to nothing, and the removed id can't be registered by someone else
+    let resolved_project_id = contract
+        .view("resolve_project_id")
+        .args_json(json!({"project_id": "project"}))
+        .await?
+        .json::<Option<String>>()?;
+    assert_eq!(resolved_project_id, None);
+
+    let reuse_outcome = attacker
+        .call(contract.id(), "add_project")
+        .args_json(json!({
+            "contract_ids": ["evil.near"],
+            "metadata": "{}",
+            "project_id": "renamed"
+        }))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?;
+    assert!(reuse_outcome.is_failure());
+
     Ok(())
 }